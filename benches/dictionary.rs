@@ -0,0 +1,55 @@
+//! Compares the dictionary backends: how long each takes to load, how fast it answers the
+//! lookups made while solving, and how much space it takes on disk.
+use std::fs;
+use std::sync::Arc;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ruzzle_solver::{Board, PATH_TO_BOARD, read_board, parse_board_and_mults};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::Dictionary;
+
+const WORDS: &str = "data/TWL06/binary.bin";
+const PREFIXES: &str = "data/prefixes/binary.bin";
+const MAPPED_WORDS: &str = "data/TWL06/words.sorted";
+const MAPPED_PREFIXES: &str = "data/prefixes/prefixes.sorted";
+const FST: &str = "data/TWL06/words.fst";
+const WORD_LIST: &str = "data/TWL06/TWL06Trimmed.txt";
+
+fn load(backend: &str) -> Dictionary {
+    match backend {
+        "hashed" => Dictionary::read_binary(backend, WORDS, PREFIXES, Alphabet::english()),
+        "mapped" => Dictionary::read_mapped(backend, MAPPED_WORDS, MAPPED_PREFIXES, Alphabet::english()),
+        "trie" => Dictionary::read_trie(backend, WORD_LIST, Alphabet::english()),
+        _ => Dictionary::read_fst(backend, FST, Alphabet::english()),
+    }.unwrap()
+}
+
+fn file_size(path: &str) -> u64 {
+    fs::metadata(path).unwrap().len()
+}
+
+fn backends(c: &mut Criterion) {
+    println!("hashed: {} bytes, mapped: {} bytes, fst: {} bytes",
+             file_size(WORDS) + file_size(PREFIXES),
+             file_size(MAPPED_WORDS) + file_size(MAPPED_PREFIXES),
+             file_size(FST));
+
+    let raw_board = read_board(PATH_TO_BOARD.to_string());
+    let (board, word_int_mults, letter_mults) = parse_board_and_mults(raw_board, &Alphabet::english());
+
+    for backend in ["hashed", "mapped", "fst", "trie"] {
+        c.bench_function(&format!("load {}", backend), |b| b.iter(|| black_box(load(backend))));
+
+        let dictionary = Arc::new(load(backend));
+        c.bench_function(&format!("solve {}", backend), |b| b.iter_batched(
+            || Board::new(board, word_int_mults, letter_mults, Arc::clone(&dictionary)),
+            |mut board| {
+                board.solve();
+                board
+            },
+            BatchSize::SmallInput,
+        ));
+    }
+}
+
+criterion_group!(benches, backends);
+criterion_main!(benches);
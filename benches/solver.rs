@@ -0,0 +1,86 @@
+//! Compares the search strategies on fixed boards: how long reading a board and the dictionary
+//! takes, how long each strategy takes to solve each board, and how long writing the found
+//! words takes.
+use std::io;
+use std::sync::Arc;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ruzzle_solver::{Board, parse_board_and_mults};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::Dictionary;
+use ruzzle_solver::strategy::{STRATEGY_NAMES, strategy_by_name};
+
+const WORDS: &str = "data/TWL06/binary.bin";
+const PREFIXES: &str = "data/prefixes/binary.bin";
+
+/// The boards solved by each strategy, with their letters and multipliers as in board.txt. The
+/// first is the board shipped in board.txt, the second is full of common letters, and the
+/// third has hardly any words.
+const BOARDS: [(&str, &str, &str); 3] = [
+    ("shipped", "HHSA UIRN NTAE ERIM", "2--- 3-T- --2D -T--"),
+    ("dense", "SERA TINE LAOD REST", "-D-- --2- T--- ---3"),
+    ("sparse", "QXZJ VWKY BCFG PHMU", "---- -2-- --D- T---"),
+];
+
+type Strategy = fn(&mut Board);
+
+/// The searches which are not a SearchStrategy, each leaving the words found in word_info.
+const SEARCHES: [(&str, Strategy); 3] = [
+    ("streaming", |board| {
+        let mut words = Vec::with_capacity(500);
+        board.solve_streaming(None, None, &mut words);
+        board.word_info = words;
+    }),
+    ("top 10", |board| board.solve_top_k(10)),
+    ("min score 30", |board| board.solve_min_score(30)),
+];
+
+fn board(dictionary: &Arc<Dictionary>, letters: &str, mults: &str) -> Board {
+    let (board, word_int_mults, letter_mults) =
+        parse_board_and_mults(vec![letters.to_string(), mults.to_string()], &dictionary.alphabet);
+    Board::new(board, word_int_mults, letter_mults, Arc::clone(dictionary))
+}
+
+fn load_dictionary() -> Dictionary {
+    Dictionary::read_binary("hashed", WORDS, PREFIXES, Alphabet::english()).unwrap()
+}
+
+/// Times solving a new board from fixture with solve.
+fn bench_solve(c: &mut Criterion, name: &str, fixture: impl Fn() -> Board, solve: impl Fn(&mut Board)) {
+    c.bench_function(name, |b| b.iter_batched(
+        &fixture,
+        |mut board| {
+            solve(&mut board);
+            board
+        },
+        BatchSize::SmallInput,
+    ));
+}
+
+fn strategies(c: &mut Criterion) {
+    c.bench_function("load dictionary", |b| b.iter(|| black_box(load_dictionary())));
+    let dictionary = Arc::new(load_dictionary());
+    let (_, letters, mults) = BOARDS[0];
+    c.bench_function("load board", |b| b.iter(|| black_box(board(&dictionary, letters, mults))));
+
+    c.bench_function("load string strategy", |b| b.iter(|| black_box(strategy_by_name("string", &dictionary))));
+    let strategies: Vec<_> = STRATEGY_NAMES.iter().map(|name| strategy_by_name(name, &dictionary).unwrap()).collect();
+
+    for (board_name, letters, mults) in BOARDS {
+        let fixture = || board(&dictionary, letters, mults);
+        for strategy in &strategies {
+            bench_solve(c, &format!("solve {} {}", board_name, strategy.name()), fixture, |board| strategy.solve(board));
+        }
+        for (search, solve) in SEARCHES {
+            bench_solve(c, &format!("solve {} {}", board_name, search), fixture, solve);
+        }
+    }
+
+    let mut solved = board(&dictionary, letters, mults);
+    solved.solve();
+    solved.sort_entries();
+    c.bench_function("write words", |b| b.iter(|| solved.write_words(&mut io::sink()).unwrap()));
+    c.bench_function("write json", |b| b.iter(|| solved.write_json(&mut io::sink()).unwrap()));
+}
+
+criterion_group!(benches, strategies);
+criterion_main!(benches);
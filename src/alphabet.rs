@@ -0,0 +1,219 @@
+//! Maps the letters of a language's edition of the game to the codes which words are packed
+//! into, along with the points each letter is worth.
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use unicode_normalization::UnicodeNormalization;
+use crate::{MAX_WORD_LEN, POINT_VALS, U64_TO_CHAR};
+
+// Codes are stored in at most six bits, and code 0 marks the end of a word.
+const MAX_SYMBOLS: usize = 63;
+// The points of a letter on a triple letter tile are kept in a u8.
+const MAX_VALUE: u8 = u8::MAX / 3;
+
+/// Returns the upper case form of letter, unless it has no single letter upper case form
+/// (eg. ß, which becomes SS).
+fn to_upper(letter: char) -> char {
+    let mut upper = letter.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => letter,
+    }
+}
+
+/// The letters of an alphabet, each with a code from 1 upwards and a point value.
+///
+/// Words are packed into a u64 with bits_per_letter bits per letter: alphabets of up to 31
+/// letters use five bits, allowing words of up to 12 letters, and larger alphabets use six bits,
+/// which limits words to 10 letters.
+#[derive(Clone, Debug)]
+pub struct Alphabet {
+    name: String,
+    // The symbol and value of the letter with code i + 1.
+    symbols: Vec<char>,
+    values: Vec<u8>,
+    codes: HashMap<char, u8>,
+    bits: u32,
+}
+
+impl Alphabet {
+    /// Creates an alphabet from its letters and their values, in order of their codes. Letters
+    /// can be worth at most 85 points, so that they can still be tripled.
+    pub fn new(name: &str, letters: &[(char, u8)]) -> Result<Alphabet, String> {
+        if letters.is_empty() || letters.len() > MAX_SYMBOLS {
+            return Err(format!("An alphabet must have between 1 and {} letters.", MAX_SYMBOLS));
+        }
+
+        let mut codes = HashMap::with_capacity(letters.len());
+        for (index, &(symbol, value)) in letters.iter().enumerate() {
+            if codes.insert(symbol, index as u8 + 1).is_some() {
+                return Err(format!("The letter {} appears twice in the alphabet.", symbol));
+            }
+            if value > MAX_VALUE {
+                return Err(format!("The letter {} is worth {} points, but letters can be worth at most {}.",
+                                   symbol, value, MAX_VALUE));
+            }
+        }
+
+        Ok(Alphabet {
+            name: name.to_string(),
+            symbols: letters.iter().map(|&(symbol, _)| symbol).collect(),
+            values: letters.iter().map(|&(_, value)| value).collect(),
+            codes,
+            bits: if letters.len() <= 31 { 5 } else { 6 },
+        })
+    }
+
+    /// Reads an alphabet from a file with one letter per line, followed by its value,
+    /// eg. "Ä 6". Letters are given codes in the order they appear.
+    pub fn read(name: &str, file_path: &str) -> Result<Alphabet, String> {
+        let file = File::open(file_path).map_err(|err| format!("Could not open {}: {}", file_path, err))?;
+        let mut letters = Vec::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|err| format!("Could not read {}: {}", file_path, err))?;
+            let line: String = line.nfc().collect();
+            let mut parts = line.split_whitespace();
+            let (symbol, value) = match (parts.next(), parts.next()) {
+                (None, _) => continue,
+                (Some(symbol), Some(value)) => (symbol, value),
+                (Some(_), None) => return Err(format!("The line \"{}\" has no letter value.", line)),
+            };
+
+            let mut chars = symbol.chars();
+            let symbol = match (chars.next(), chars.next()) {
+                (Some(symbol), None) => to_upper(symbol),
+                _ => return Err(format!("\"{}\" is not a single letter.", symbol)),
+            };
+            let value = value.parse().map_err(|_| format!("\"{}\" is not a letter value.", value))?;
+            letters.push((symbol, value));
+        }
+
+        Alphabet::new(name, &letters)
+    }
+
+    /// The alphabet of the English edition, matching the codes of the binary dictionary files.
+    pub fn english() -> Alphabet {
+        let letters: Vec<(char, u8)> = (1..27).map(|code| (U64_TO_CHAR[code], POINT_VALS[code])).collect();
+        Alphabet::new("en", &letters).unwrap()
+    }
+
+    pub fn french() -> Alphabet {
+        Alphabet::new("fr", &[('A', 1), ('B', 3), ('C', 3), ('D', 2), ('E', 1), ('F', 4), ('G', 2),
+            ('H', 4), ('I', 1), ('J', 8), ('K', 10), ('L', 1), ('M', 2), ('N', 1), ('O', 1), ('P', 3),
+            ('Q', 8), ('R', 1), ('S', 1), ('T', 1), ('U', 1), ('V', 4), ('W', 10), ('X', 10), ('Y', 10),
+            ('Z', 10)]).unwrap()
+    }
+
+    pub fn german() -> Alphabet {
+        Alphabet::new("de", &[('A', 1), ('B', 3), ('C', 4), ('D', 1), ('E', 1), ('F', 4), ('G', 2),
+            ('H', 2), ('I', 1), ('J', 6), ('K', 4), ('L', 2), ('M', 3), ('N', 1), ('O', 2), ('P', 4),
+            ('Q', 10), ('R', 1), ('S', 1), ('T', 1), ('U', 1), ('V', 6), ('W', 3), ('X', 8), ('Y', 10),
+            ('Z', 3), ('Ä', 6), ('Ö', 8), ('Ü', 6)]).unwrap()
+    }
+
+    pub fn italian() -> Alphabet {
+        Alphabet::new("it", &[('A', 1), ('B', 5), ('C', 2), ('D', 5), ('E', 1), ('F', 5), ('G', 8),
+            ('H', 8), ('I', 1), ('L', 3), ('M', 3), ('N', 3), ('O', 1), ('P', 5), ('Q', 10), ('R', 2),
+            ('S', 2), ('T', 2), ('U', 3), ('V', 5), ('Z', 8)]).unwrap()
+    }
+
+    pub fn spanish() -> Alphabet {
+        Alphabet::new("es", &[('A', 1), ('B', 3), ('C', 3), ('D', 2), ('E', 1), ('F', 4), ('G', 2),
+            ('H', 4), ('I', 1), ('J', 8), ('L', 1), ('M', 3), ('N', 1), ('Ñ', 8), ('O', 1), ('P', 3),
+            ('Q', 5), ('R', 1), ('S', 1), ('T', 1), ('U', 1), ('V', 4), ('X', 8), ('Y', 4),
+            ('Z', 10)]).unwrap()
+    }
+
+    pub fn swedish() -> Alphabet {
+        Alphabet::new("sv", &[('A', 1), ('B', 4), ('C', 8), ('D', 1), ('E', 1), ('F', 3), ('G', 2),
+            ('H', 2), ('I', 1), ('J', 7), ('K', 2), ('L', 1), ('M', 2), ('N', 1), ('O', 2), ('P', 4),
+            ('R', 1), ('S', 1), ('T', 1), ('U', 4), ('V', 3), ('X', 8), ('Y', 7), ('Z', 8), ('Å', 4),
+            ('Ä', 3), ('Ö', 4)]).unwrap()
+    }
+
+    /// Returns the built in alphabet with the given language code (eg. "sv").
+    pub fn by_name(name: &str) -> Option<Alphabet> {
+        match name {
+            "en" => Some(Alphabet::english()),
+            "fr" => Some(Alphabet::french()),
+            "de" => Some(Alphabet::german()),
+            "it" => Some(Alphabet::italian()),
+            "es" => Some(Alphabet::spanish()),
+            "sv" => Some(Alphabet::swedish()),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// The number of bits each letter takes in a packed word.
+    pub fn bits_per_letter(&self) -> u32 {
+        self.bits
+    }
+
+    /// The longest word which fits in a u64 using this alphabet.
+    pub fn max_word_len(&self) -> u8 {
+        MAX_WORD_LEN.min((64 / self.bits) as u8)
+    }
+
+    /// Returns the code of a letter, folding lower case letters to upper case. Accented letters
+    /// which are not part of the alphabet are folded to their unaccented letter (eg. É to E in
+    /// English), while those which are part of it are kept distinct (eg. Ä in German).
+    pub fn code(&self, letter: char) -> Option<u8> {
+        let letter = to_upper(letter);
+        self.codes.get(&letter).copied().or_else(|| {
+            let base = std::iter::once(letter).nfd().next()?;
+            self.codes.get(&base).copied()
+        })
+    }
+
+    pub fn symbol(&self, code: u8) -> char {
+        self.symbols[code as usize - 1]
+    }
+
+    /// The points the letter with the given code is worth.
+    pub fn value(&self, code: u8) -> u8 {
+        self.values[code as usize - 1]
+    }
+
+    /// Packs a word into a u64, or returns None if the word contains a letter outside of the
+    /// alphabet or is too long. The word is normalised first, so that decomposed letters
+    /// (eg. A followed by a combining diaeresis) are read as the letter they make up.
+    pub fn encode(&self, word: &str) -> Option<u64> {
+        let mut encoded = 0;
+        let mut len = 0;
+        for letter in word.nfc() {
+            encoded = (encoded << self.bits) | self.code(letter)? as u64;
+            len += 1;
+            if len > self.max_word_len() {
+                return None;
+            }
+        }
+        Some(encoded)
+    }
+
+    /// Generates the string representation of a word packed by encode.
+    pub fn decode(&self, word: u64) -> String {
+        let mask = (1 << self.bits) - 1;
+        let mut letters = Vec::with_capacity(MAX_WORD_LEN as usize);
+        let mut remaining = word;
+
+        while remaining & mask != 0 {
+            letters.push(self.symbol((remaining & mask) as u8));
+            remaining >>= self.bits;
+        }
+
+        letters.iter().rev().collect()
+    }
+}
@@ -0,0 +1,189 @@
+//! Solving modes which only keep the best words, pruning any branch of the search which
+//! can not possibly produce a word worth keeping.
+use crate::{Board, BOARD_SIZE, MIN_WORD_LEN, StackEntry, gen_graph, word_score};
+use crate::store::WordStore;
+
+/// Receives the words found by bounded_dfs, and decides which score a word needs to be kept.
+trait Collector {
+    /// Words scoring less than the threshold are discarded, and branches which can not reach
+    /// the threshold are not explored.
+    fn threshold(&self) -> u16;
+
+    fn push(&mut self, word: u64, score: u16, path: u64);
+}
+
+/// Keeps every word scoring at least min_score, including repeated words with different paths.
+struct MinScore {
+    min_score: u16,
+    entries: Vec<(u64, u16, u64)>,
+}
+
+impl Collector for MinScore {
+    fn threshold(&self) -> u16 {
+        self.min_score
+    }
+
+    fn push(&mut self, word: u64, score: u16, path: u64) {
+        self.entries.push((word, score, path));
+    }
+}
+
+/// Keeps the k best distinct words, along with the path giving each word its best score.
+struct TopK {
+    k: usize,
+    // Sorted in descending order of score.
+    entries: Vec<(u64, u16, u64)>,
+}
+
+impl Collector for TopK {
+    fn threshold(&self) -> u16 {
+        if self.entries.len() < self.k {
+            0
+        } else {
+            // A word must beat the worst kept word to displace it.
+            self.entries[self.k - 1].1.saturating_add(1)
+        }
+    }
+
+    fn push(&mut self, word: u64, score: u16, path: u64) {
+        if let Some(index) = self.entries.iter().position(|entry| entry.0 == word) {
+            if self.entries[index].1 >= score {
+                return;
+            }
+            self.entries.remove(index);
+        } else if self.entries.len() == self.k {
+            self.entries.pop();
+        }
+
+        let index = self.entries.iter().position(|entry| entry.1 < score).unwrap_or(self.entries.len());
+        self.entries.insert(index, (word, score, path));
+    }
+}
+
+/// The points and word multipliers of the board, each sorted in descending order
+/// along with the vertex they belong to.
+struct SortedTiles {
+    points: [(u8, u8); BOARD_SIZE * BOARD_SIZE],
+    mults: [(u8, u8); BOARD_SIZE * BOARD_SIZE],
+    max_word_len: u8,
+}
+
+impl SortedTiles {
+    fn new(board: &Board) -> SortedTiles {
+        let mut points = [(0, 0); BOARD_SIZE * BOARD_SIZE];
+        let mut mults = [(0, 0); BOARD_SIZE * BOARD_SIZE];
+        for i in 0..BOARD_SIZE * BOARD_SIZE {
+            points[i] = (board.points[i], i as u8);
+            mults[i] = (board.word_int_mults[i], i as u8);
+        }
+        points.sort_by(|a, b| b.cmp(a));
+        mults.sort_by(|a, b| b.cmp(a));
+        SortedTiles { points, mults, max_word_len: board.alphabet().max_word_len() }
+    }
+
+    /// Returns an upper bound on the score of any word which extends a word of word_len letters
+    /// with word_pts points and a multiplier of word_mult, using only the vertices not in visited.
+    /// The bound assumes that the best remaining letters and multipliers can all be reached.
+    fn score_bound(&self, word_pts: u16, word_mult: u16, word_len: u8, visited: u16) -> u16 {
+        let free = (BOARD_SIZE * BOARD_SIZE) as u8 - visited.count_ones() as u8;
+        let slots = free.min(self.max_word_len - word_len) as usize;
+
+        let unvisited = |&&(_, vertex): &&(u8, u8)| (visited >> vertex) & 1 == 0;
+        let extra_pts: u64 = self.points.iter().filter(unvisited).take(slots)
+            .map(|&(pts, _)| pts as u64).sum();
+        let extra_mult: u64 = self.mults.iter().filter(unvisited).take(slots)
+            .map(|&(mult, _)| mult as u64).product();
+
+        let max_len = word_len as u64 + slots as u64;
+        let bonus = if max_len > 4 { 5 * (max_len - 4) } else { 0 };
+        let bound = (word_pts as u64 + extra_pts) * word_mult as u64 * extra_mult + bonus;
+        bound.min(u16::MAX as u64) as u16
+    }
+}
+
+/// A non recursive depth first search which passes every word of store scoring at least the
+/// collector's threshold to the collector. Branches whose score bound falls below the threshold
+/// are skipped.
+fn bounded_dfs<S: WordStore, C: Collector>(board: &Board, store: &S, graph: &[Vec<u8>], collector: &mut C) {
+    let tiles = SortedTiles::new(board);
+    let bits = board.alphabet().bits_per_letter();
+    let max_word_len = board.alphabet().max_word_len();
+    let mut stack: Vec<StackEntry<S::State>> = Vec::with_capacity(120);
+    for i in 0..BOARD_SIZE * BOARD_SIZE {
+        if let Some(state) = store.step(store.start(), board.board[i]) {
+            stack.push(((0b10000 | i) as u64,
+                        board.board[i] as u64,
+                        state,
+                        board.points[i] as u16,
+                        board.word_int_mults[i] as u16,
+                        1, 0));
+        }
+    }
+
+    while let Some((path, word, state, word_pts, word_mult, mut word_len, mut visited)) = stack.pop() {
+        if (MIN_WORD_LEN..=max_word_len).contains(&word_len) && store.ends_word(state) {
+            let score = word_score(word_pts, word_mult, word_len);
+
+            if score >= collector.threshold() {
+                collector.push(word, score, path);
+            }
+        }
+
+        let vert = path & 0xF;
+        visited |= 1 << vert;
+
+        if word_len == max_word_len
+            || tiles.score_bound(word_pts, word_mult, word_len, visited) < collector.threshold() {
+            continue;
+        }
+
+        word_len += 1;
+
+        for &vertex in &graph[vert as usize] {
+            if ((visited >> vertex) & 1) == 0 {
+                let temp_word = (word << bits) | (board.board[vertex as usize] as u64);
+
+                let temp_state = match store.step(state, board.board[vertex as usize]) {
+                    Some(temp_state) => temp_state,
+                    None => continue,
+                };
+
+                stack.push(((path << 5) | 0b10000 | (vertex as u64), temp_word, temp_state,
+                            word_pts + board.points[vertex as usize] as u16,
+                            word_mult.saturating_mul(board.word_int_mults[vertex as usize] as u16), word_len,
+                            visited | (1 << vertex)));
+            }
+        }
+    }
+}
+
+impl Board {
+    /// Finds the k highest scoring distinct words in the board, replacing any previously found
+    /// words. Each word is kept with the path that gives it the highest score, and the words are
+    /// left sorted in descending order of score.
+    pub fn solve_top_k(&mut self, k: usize) {
+        self.word_info.clear();
+        if k == 0 {
+            return;
+        }
+
+        let mut collector = TopK { k, entries: Vec::with_capacity(k + 1) };
+        with_store!(self.dictionary, |store| bounded_dfs(self, store, &gen_graph(), &mut collector));
+        let alphabet = &self.dictionary.alphabet;
+        self.word_info = collector.entries.into_iter()
+            .map(|(word, score, path)| (alphabet.decode(word), score, path))
+            .collect();
+    }
+
+    /// Finds every word in the board which scores at least min_score, replacing any previously
+    /// found words.
+    pub fn solve_min_score(&mut self, min_score: u16) {
+        self.word_info.clear();
+        let mut collector = MinScore { min_score, entries: Vec::with_capacity(500) };
+        with_store!(self.dictionary, |store| bounded_dfs(self, store, &gen_graph(), &mut collector));
+        let alphabet = &self.dictionary.alphabet;
+        self.word_info = collector.entries.into_iter()
+            .map(|(word, score, path)| (alphabet.decode(word), score, path))
+            .collect();
+    }
+}
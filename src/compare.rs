@@ -0,0 +1,109 @@
+//! Compares the words two players entered on the same board, with each other and with every
+//! word the solver found in the board.
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use crate::Board;
+
+/// How two players' words compare on a board. Each list of words is sorted best first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub names: (String, String),
+    pub only_a: Vec<(String, u16)>,
+    pub only_b: Vec<(String, u16)>,
+    pub both: Vec<(String, u16)>,
+    /// The best words which neither player found.
+    pub missed: Vec<(String, u16)>,
+    /// The words entered by each player which are not in the board, and so did not score.
+    pub invalid: (Vec<String>, Vec<String>),
+    pub scores: (u32, u32),
+    /// The score for finding every word in the board.
+    pub max_score: u32,
+}
+
+impl Comparison {
+    /// The share of the maximum score each player scored, as a percentage.
+    pub fn shares(&self) -> (f32, f32) {
+        let share = |score: u32| if self.max_score == 0 { 0. } else { 100. * score as f32 / self.max_score as f32 };
+        (share(self.scores.0), share(self.scores.1))
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let (name_a, name_b) = &self.names;
+        let (share_a, share_b) = self.shares();
+        writeln!(out, "{} scored {} points ({:.1}% of the {} in the board), and {} scored {} points ({:.1}%).",
+                 name_a, self.scores.0, share_a, self.max_score, name_b, self.scores.1, share_b)?;
+        for (title, words) in [(format!("Only {} found", name_a), &self.only_a), (format!("Only {} found", name_b), &self.only_b),
+                               ("Both found".to_string(), &self.both), ("Neither found".to_string(), &self.missed)] {
+            writeln!(out, "{} ({} {}):", title, words.len(), if words.len() == 1 { "word" } else { "words" })?;
+            for (word, score) in words {
+                writeln!(out, "  {:<12} {:>4}", word, score)?;
+            }
+        }
+        for (name, invalid) in [(name_a, &self.invalid.0), (name_b, &self.invalid.1)] {
+            if !invalid.is_empty() {
+                writeln!(out, "Not in the board, from {}: {}", name, invalid.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sorts words best first, then alphabetically.
+fn sort_words(words: &mut [(String, u16)]) {
+    words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+}
+
+/// Compares the words entered by two players on a solved board, listing at most missed_words
+/// of the best words neither found. Words count once, however often they were entered, and
+/// each scores its best score in the board.
+pub fn compare<A: AsRef<str>, B: AsRef<str>>(board: &Board, names: (&str, &str), words_a: &[A], words_b: &[B],
+                                             missed_words: usize) -> Comparison {
+    let mut best: HashMap<&str, u16> = HashMap::with_capacity(board.word_info.len());
+    for (word, score, _) in &board.word_info {
+        let entry = best.entry(word.as_str()).or_insert(*score);
+        *entry = (*entry).max(*score);
+    }
+
+    // Splits a player's words into those in the board and those not.
+    let split = |words: Vec<&str>| -> (HashSet<String>, Vec<String>) {
+        let mut valid = HashSet::new();
+        let mut invalid = Vec::new();
+        for word in words.into_iter().map(|word| word.trim().to_uppercase()).filter(|word| !word.is_empty()) {
+            if best.contains_key(word.as_str()) {
+                valid.insert(word);
+            } else if !invalid.contains(&word) {
+                invalid.push(word);
+            }
+        }
+        (valid, invalid)
+    };
+    let (valid_a, invalid_a) = split(words_a.iter().map(AsRef::as_ref).collect());
+    let (valid_b, invalid_b) = split(words_b.iter().map(AsRef::as_ref).collect());
+
+    let (mut only_a, mut only_b, mut both, mut missed) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for (&word, &score) in &best {
+        let list = match (valid_a.contains(word), valid_b.contains(word)) {
+            (true, true) => &mut both,
+            (true, false) => &mut only_a,
+            (false, true) => &mut only_b,
+            (false, false) => &mut missed,
+        };
+        list.push((word.to_string(), score));
+    }
+    for words in [&mut only_a, &mut only_b, &mut both, &mut missed] {
+        sort_words(words);
+    }
+    missed.truncate(missed_words);
+
+    let total = |words: &[(String, u16)]| words.iter().map(|(_, score)| *score as u32).sum::<u32>();
+    Comparison {
+        names: (names.0.to_string(), names.1.to_string()),
+        scores: (total(&only_a) + total(&both), total(&only_b) + total(&both)),
+        max_score: best.values().map(|&score| score as u32).sum(),
+        only_a,
+        only_b,
+        both,
+        missed,
+        invalid: (invalid_a, invalid_b),
+    }
+}
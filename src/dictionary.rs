@@ -0,0 +1,492 @@
+//! Dictionaries which can be chosen by name at runtime, as listed in a configuration file.
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::hash::BuildHasherDefault;
+use std::io::{BufRead, BufReader};
+use std::sync::Arc;
+use fnv::{FnvHashMap, FnvHashSet, FnvHasher};
+use crate::alphabet::Alphabet;
+use crate::mapped::MappedWords;
+use crate::metadata::Metadata;
+use crate::store::{HashStore, PackedWords, SortedStore, Trie, WordStore, insert_prefixes, word_len};
+use crate::transducer::FstWords;
+use crate::{MIN_WORD_LEN, read_binary_words};
+
+pub const PATH_TO_CONFIG: &str = r"data/dictionaries.txt";
+pub const DEFAULT_DICTIONARY: &str = "twl06";
+
+pub type WordSet = HashSet<u64, BuildHasherDefault<FnvHasher>>;
+
+/// The store a dictionary's words are kept in.
+pub enum Store {
+    Hashed(HashStore),
+    Sorted(SortedStore),
+    Trie(Trie),
+    Fst(FstWords),
+}
+
+impl Store {
+    /// The packed words in the store, in no particular order.
+    pub fn words(&self) -> Vec<u64> {
+        match self {
+            Store::Hashed(store) => store.words.packed_words(),
+            Store::Sorted(store) => store.words.packed_words(),
+            Store::Trie(trie) => trie.words(),
+            Store::Fst(fst) => fst.words(),
+        }
+    }
+
+    /// The prefixes of length PREFIX_LOWER_BOUND to PREFIX_UPPER_BOUND of the words in the store.
+    pub fn prefixes(&self) -> WordSet {
+        match self {
+            Store::Hashed(store) => store.prefixes.clone(),
+            Store::Sorted(store) => store.prefixes.iter().collect(),
+            Store::Trie(_) | Store::Fst(_) => {
+                let bits = self.bits_per_letter();
+                let mut prefixes = FnvHashSet::default();
+                for word in self.words() {
+                    insert_prefixes(&mut prefixes, word, bits);
+                }
+                prefixes
+            }
+        }
+    }
+
+    pub fn bits_per_letter(&self) -> u32 {
+        match self {
+            Store::Hashed(store) => store.bits_per_letter(),
+            Store::Sorted(store) => store.bits_per_letter(),
+            Store::Trie(trie) => trie.bits_per_letter(),
+            Store::Fst(fst) => fst.bits_per_letter(),
+        }
+    }
+
+    pub fn is_word(&self, word: u64) -> bool {
+        match self {
+            Store::Hashed(store) => store.is_word(word),
+            Store::Sorted(store) => store.is_word(word),
+            Store::Trie(trie) => trie.is_word(word),
+            Store::Fst(fst) => fst.is_word(word),
+        }
+    }
+
+    pub fn is_prefix(&self, word: u64) -> bool {
+        match self {
+            Store::Hashed(store) => store.is_prefix(word),
+            Store::Sorted(store) => store.is_prefix(word),
+            Store::Trie(trie) => trie.is_prefix(word),
+            Store::Fst(fst) => fst.is_prefix(word),
+        }
+    }
+}
+
+/// A set of words, along with their prefixes, packed using the dictionary's alphabet.
+pub struct Dictionary {
+    pub name: String,
+    pub alphabet: Alphabet,
+    pub store: Store,
+    // The name of the base dictionary, followed by the names of the allow lists merged into it.
+    layers: Vec<String>,
+    // The index in layers of each word added by an allow list. Words not in here come from the base.
+    origins: FnvHashMap<u64, u8>,
+    metadata: Option<Metadata>,
+}
+
+/// Whether a layer adds its words to a dictionary or removes them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayerKind {
+    Allow,
+    Deny,
+}
+
+/// A list of words to add to or remove from a base dictionary, eg. house rule words or
+/// offensive words.
+#[derive(Clone, Debug)]
+pub struct Layer {
+    pub kind: LayerKind,
+    pub path: String,
+}
+
+/// Reads a list of words, one per line, packed using alphabet. Words which do not fit in the
+/// alphabet, or are too short or too long, are skipped.
+fn read_words(file_path: &str, alphabet: &Alphabet) -> Result<Vec<u64>, String> {
+    let file = File::open(file_path).map_err(|err| format!("Could not open {}: {}", file_path, err))?;
+    let mut words = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|err| format!("Could not read {}: {}", file_path, err))?;
+        match alphabet.encode(line.trim()) {
+            Some(word) if word_len(word, alphabet.bits_per_letter()) >= MIN_WORD_LEN => words.push(word),
+            _ => continue,
+        }
+    }
+
+    Ok(words)
+}
+
+impl Dictionary {
+    pub fn new(name: &str, alphabet: Alphabet, store: Store) -> Dictionary {
+        Dictionary {
+            name: name.to_string(),
+            alphabet,
+            store,
+            layers: vec![name.to_string()],
+            origins: FnvHashMap::default(),
+            metadata: None,
+        }
+    }
+
+    /// Attaches details of the dictionary's words, such as their definitions.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Dictionary {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Reads a dictionary with one word per line, generating the prefixes of length
+    /// PREFIX_LOWER_BOUND to PREFIX_UPPER_BOUND. Words which do not fit in the alphabet,
+    /// or are too short or too long, are skipped.
+    pub fn read_word_list(name: &str, file_path: &str, alphabet: Alphabet) -> Result<Dictionary, String> {
+        let words = read_words(file_path, &alphabet)?.into_iter().collect();
+        let store = HashStore::from_words(words, alphabet.bits_per_letter());
+        Ok(Dictionary::new(name, alphabet, Store::Hashed(store)))
+    }
+
+    /// Reads a dictionary with one word per line into a trie. Words which do not fit in the
+    /// alphabet, or are too short or too long, are skipped.
+    pub fn read_trie(name: &str, file_path: &str, alphabet: Alphabet) -> Result<Dictionary, String> {
+        let trie = Trie::from_words(read_words(file_path, &alphabet)?, alphabet.bits_per_letter());
+        Ok(Dictionary::new(name, alphabet, Store::Trie(trie)))
+    }
+
+    /// Reads a dictionary from binary files of packed words and prefixes, as generated
+    /// by preprocessing/merge_prefixes.py.
+    pub fn read_binary(name: &str, words_path: &str, prefixes_path: &str, alphabet: Alphabet) -> Result<Dictionary, String> {
+        let prefixes = read_binary_words(prefixes_path)
+            .map_err(|err| format!("Could not read {}: {}", prefixes_path, err))?;
+        let words = read_binary_words(words_path)
+            .map_err(|err| format!("Could not read {}: {}", words_path, err))?;
+        let store = HashStore::new(words, prefixes, alphabet.bits_per_letter());
+        Ok(Dictionary::new(name, alphabet, Store::Hashed(store)))
+    }
+
+    /// Maps a dictionary from sorted tables of packed words and prefixes, as written by
+    /// write_mapped. Unlike the other formats, no set has to be built, so this takes about the
+    /// same time whatever the size of the dictionary.
+    pub fn read_mapped(name: &str, words_path: &str, prefixes_path: &str, alphabet: Alphabet) -> Result<Dictionary, String> {
+        let prefixes = MappedWords::open(prefixes_path)
+            .map_err(|err| format!("Could not map {}: {}", prefixes_path, err))?;
+        let words = MappedWords::open(words_path)
+            .map_err(|err| format!("Could not map {}: {}", words_path, err))?;
+        let store = SortedStore::new(words, prefixes, alphabet.bits_per_letter());
+        Ok(Dictionary::new(name, alphabet, Store::Sorted(store)))
+    }
+
+    /// Maps a dictionary from a transducer, as written by write_fst, which serves both the
+    /// words and the prefixes.
+    pub fn read_fst(name: &str, file_path: &str, alphabet: Alphabet) -> Result<Dictionary, String> {
+        let fst = FstWords::open(file_path, alphabet.bits_per_letter())
+            .map_err(|err| format!("Could not map {}: {}", file_path, err))?;
+        Ok(Dictionary::new(name, alphabet, Store::Fst(fst)))
+    }
+
+    /// Writes the words to a transducer which can be read by read_fst.
+    pub fn write_fst(&self, file_path: &str) -> Result<(), String> {
+        FstWords::write(file_path, self.store.words(), self.alphabet.bits_per_letter())
+            .map_err(|err| format!("Could not write {}: {}", file_path, err))
+    }
+
+    /// Writes the words and prefixes to sorted tables which can be read by read_mapped.
+    pub fn write_mapped(&self, words_path: &str, prefixes_path: &str) -> Result<(), String> {
+        MappedWords::write(words_path, self.store.words())
+            .map_err(|err| format!("Could not write {}: {}", words_path, err))?;
+        MappedWords::write(prefixes_path, self.store.prefixes())
+            .map_err(|err| format!("Could not write {}: {}", prefixes_path, err))
+    }
+
+    /// Creates a dictionary named name from the words of base, with the words of each layer
+    /// added or removed in order, so a later layer overrides an earlier one.
+    ///
+    /// The prefixes of added words are added too. If any word is removed, the prefixes are
+    /// generated again from the remaining words, so that the search does not follow prefixes
+    /// which no longer lead to a word.
+    pub fn with_layers(name: &str, base: &Dictionary, layers: &[Layer]) -> Result<Dictionary, String> {
+        let bits = base.alphabet.bits_per_letter();
+        let alphabet = base.alphabet.clone();
+        let mut prefixes = base.store.prefixes();
+        let mut words: WordSet = base.store.words().into_iter().collect();
+        let mut layer_names = base.layers.clone();
+        let mut origins = base.origins.clone();
+        let mut removed_any = false;
+
+        for layer in layers {
+            let listed = read_words(&layer.path, &alphabet)?;
+            match layer.kind {
+                LayerKind::Allow => {
+                    if layer_names.len() > u8::MAX as usize {
+                        return Err(format!("{} has too many allow lists.", name));
+                    }
+                    let index = layer_names.len() as u8;
+                    layer_names.push(layer.path.clone());
+                    for word in listed {
+                        if words.insert(word) {
+                            origins.insert(word, index);
+                            insert_prefixes(&mut prefixes, word, bits);
+                        }
+                    }
+                }
+                LayerKind::Deny => {
+                    for word in listed {
+                        if words.remove(&word) {
+                            origins.remove(&word);
+                            removed_any = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        if removed_any {
+            prefixes.clear();
+            for &word in &words {
+                insert_prefixes(&mut prefixes, word, bits);
+            }
+        }
+
+        Ok(Dictionary {
+            name: name.to_string(),
+            alphabet,
+            store: Store::Hashed(HashStore::new(words, prefixes, bits)),
+            layers: layer_names,
+            origins,
+            metadata: base.metadata.clone(),
+        })
+    }
+
+    /// The names of the layers words can come from: the base dictionary, followed by the
+    /// allow lists merged into it.
+    pub fn layers(&self) -> &[String] {
+        &self.layers
+    }
+
+    /// Returns the name of the layer a packed word came from, or None if the word is not
+    /// in the dictionary.
+    pub fn layer_of(&self, word: u64) -> Option<&str> {
+        if !self.store.is_word(word) {
+            return None;
+        }
+        let index = self.origins.get(&word).copied().unwrap_or(0);
+        Some(&self.layers[index as usize])
+    }
+}
+
+/// Where a dictionary's words are read from.
+#[derive(Clone, Debug)]
+pub enum Source {
+    /// Binary files of packed words and prefixes.
+    Binary { words: String, prefixes: String },
+    /// Sorted tables of packed words and prefixes, which are memory mapped.
+    Mapped { words: String, prefixes: String },
+    /// A transducer holding the words, which is memory mapped.
+    Fst(String),
+    /// A text file with one word per line.
+    WordList(String),
+    /// A text file with one word per line, which is read into a trie.
+    Trie(String),
+    /// Another registered dictionary, with words added and removed by allow and deny lists.
+    Layered { base: String, layers: Vec<Layer> },
+}
+
+/// Everything needed to load a dictionary.
+#[derive(Clone, Debug)]
+pub struct DictionaryConfig {
+    pub name: String,
+    pub alphabet: Alphabet,
+    pub source: Source,
+    /// A file of definitions and other details of the words, as read by Metadata::read.
+    pub metadata: Option<String>,
+}
+
+impl DictionaryConfig {
+    /// Loads the dictionary, taking the base of a layered dictionary from registry.
+    pub fn load(&self, registry: &mut Registry) -> Result<Dictionary, String> {
+        let dictionary = self.load_words(registry)?;
+        match &self.metadata {
+            Some(path) => Ok(dictionary.with_metadata(Metadata::read(path, self.alphabet.clone())?)),
+            None => Ok(dictionary),
+        }
+    }
+
+    fn load_words(&self, registry: &mut Registry) -> Result<Dictionary, String> {
+        match &self.source {
+            Source::Binary { words, prefixes } =>
+                Dictionary::read_binary(&self.name, words, prefixes, self.alphabet.clone()),
+            Source::Mapped { words, prefixes } =>
+                Dictionary::read_mapped(&self.name, words, prefixes, self.alphabet.clone()),
+            Source::Fst(path) =>
+                Dictionary::read_fst(&self.name, path, self.alphabet.clone()),
+            Source::WordList(path) =>
+                Dictionary::read_word_list(&self.name, path, self.alphabet.clone()),
+            Source::Trie(path) =>
+                Dictionary::read_trie(&self.name, path, self.alphabet.clone()),
+            Source::Layered { base, layers } => {
+                let base = registry.get(base)?;
+                if base.alphabet.name() != self.alphabet.name() {
+                    return Err(format!("{} uses the {} alphabet, but {} uses the {} alphabet.",
+                                       base.name, base.alphabet.name(), self.name, self.alphabet.name()));
+                }
+                Dictionary::with_layers(&self.name, &base, layers)
+            }
+        }
+    }
+}
+
+/// Dictionaries registered by name. Each dictionary is loaded the first time it is asked
+/// for, and is then shared by every board which uses it.
+#[derive(Default)]
+pub struct Registry {
+    configs: Vec<DictionaryConfig>,
+    loaded: HashMap<String, Arc<Dictionary>>,
+    // The dictionaries being loaded, used to catch layered dictionaries which depend on themselves.
+    loading: Vec<String>,
+}
+
+impl Registry {
+    /// A registry holding the TWL06 dictionary shipped in data/, as memory mapped tables.
+    pub fn with_defaults() -> Registry {
+        let mut registry = Registry::default();
+        registry.register(DictionaryConfig {
+            name: DEFAULT_DICTIONARY.to_string(),
+            alphabet: Alphabet::english(),
+            source: Source::Mapped {
+                words: "./data/TWL06/words.sorted".to_string(),
+                prefixes: "./data/prefixes/prefixes.sorted".to_string(),
+            },
+            metadata: None,
+        });
+        registry
+    }
+
+    /// Reads a configuration file, in which each line registers a dictionary as either
+    ///
+    /// `NAME ALPHABET binary|mapped WORDS_PATH PREFIXES_PATH`, `NAME ALPHABET words|trie WORD_LIST_PATH`,
+    /// `NAME ALPHABET fst FST_PATH` or
+    /// `NAME ALPHABET layered BASE (allow|deny WORD_LIST_PATH)...`,
+    ///
+    /// where ALPHABET is the name of a built in alphabet, or the path of an alphabet file, and
+    /// BASE is the name of a dictionary registered earlier in the file. Any of these may be
+    /// followed by `metadata METADATA_PATH`, naming a file of definitions of the words.
+    /// Blank lines and lines starting with # are ignored.
+    pub fn read_config(file_path: &str) -> Result<Registry, String> {
+        let file = File::open(file_path).map_err(|err| format!("Could not open {}: {}", file_path, err))?;
+        let mut registry = Registry::default();
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|err| format!("Could not read {}: {}", file_path, err))?;
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.is_empty() || parts[0].starts_with('#') {
+                continue;
+            }
+
+            // Any dictionary may be followed by the path of its metadata file.
+            let (parts, metadata) = match parts[..] {
+                [ref rest @ .., "metadata", path] => (rest, Some(path.to_string())),
+                ref all => (all, None),
+            };
+            if parts.len() < 3 {
+                return Err(format!("The line \"{}\" does not describe a dictionary.", line));
+            }
+            let source = match parts[2..] {
+                ["binary", words, prefixes] => Source::Binary { words: words.to_string(), prefixes: prefixes.to_string() },
+                ["mapped", words, prefixes] => Source::Mapped { words: words.to_string(), prefixes: prefixes.to_string() },
+                ["words", path] => Source::WordList(path.to_string()),
+                ["fst", path] => Source::Fst(path.to_string()),
+                ["trie", path] => Source::Trie(path.to_string()),
+                ["layered", base, ref layers @ ..] if !layers.is_empty() && layers.len() % 2 == 0 => {
+                    let layers = layers.chunks(2).map(|layer| match layer {
+                        ["allow", path] => Ok(Layer { kind: LayerKind::Allow, path: path.to_string() }),
+                        ["deny", path] => Ok(Layer { kind: LayerKind::Deny, path: path.to_string() }),
+                        _ => Err(format!("The line \"{}\" does not describe a dictionary.", line)),
+                    }).collect::<Result<_, _>>()?;
+                    Source::Layered { base: base.to_string(), layers }
+                }
+                _ => return Err(format!("The line \"{}\" does not describe a dictionary.", line)),
+            };
+            let alphabet = match Alphabet::by_name(parts[1]) {
+                Some(alphabet) => alphabet,
+                None => Alphabet::read(parts[1], parts[1])?,
+            };
+
+            registry.register(DictionaryConfig { name: parts[0].to_string(), alphabet, source, metadata });
+        }
+
+        Ok(registry)
+    }
+
+    /// Adds a dictionary to the registry, replacing any dictionary with the same name. The
+    /// dictionaries layered on a replaced dictionary are loaded again the next time they are
+    /// asked for, while those already handed out keep the words they were loaded with.
+    pub fn register(&mut self, config: DictionaryConfig) {
+        let stale: Vec<String> = self.configs.iter()
+            .filter(|existing| self.layered_on(existing, &config.name))
+            .map(|existing| existing.name.clone())
+            .collect();
+        for name in stale.iter().chain([&config.name]) {
+            self.loaded.remove(name);
+        }
+        self.configs.retain(|existing| existing.name != config.name);
+        self.configs.push(config);
+    }
+
+    /// Whether config is layered on the dictionary named base, directly or through other
+    /// layered dictionaries.
+    fn layered_on(&self, config: &DictionaryConfig, base: &str) -> bool {
+        let mut config = config;
+        // A chain longer than the registry has dictionaries goes round in a cycle.
+        for _ in 0..self.configs.len() {
+            match &config.source {
+                Source::Layered { base: next, .. } if next == base => return true,
+                Source::Layered { base: next, .. } => match self.config(next) {
+                    Ok(next) => config = next,
+                    Err(_) => return false,
+                },
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// The names of the registered dictionaries, in the order they were registered.
+    pub fn names(&self) -> Vec<&str> {
+        self.configs.iter().map(|config| config.name.as_str()).collect()
+    }
+
+    /// Returns how the dictionary with the given name is loaded.
+    pub fn config(&self, name: &str) -> Result<&DictionaryConfig, String> {
+        self.configs.iter().find(|config| config.name == name)
+            .ok_or_else(|| format!("No dictionary is named {}. The dictionaries are: {}.",
+                                   name, self.names().join(", ")))
+    }
+
+    /// Returns the dictionary with the given name, loading it if it has not been loaded yet.
+    pub fn get(&mut self, name: &str) -> Result<Arc<Dictionary>, String> {
+        if let Some(dictionary) = self.loaded.get(name) {
+            return Ok(Arc::clone(dictionary));
+        }
+
+        let config = self.config(name)?.clone();
+        if self.loading.iter().any(|loading| loading == name) {
+            return Err(format!("The dictionaries {} are layered on top of each other.", self.loading.join(", ")));
+        }
+
+        self.loading.push(name.to_string());
+        let dictionary = config.load(self);
+        self.loading.pop();
+        let dictionary = Arc::new(dictionary?);
+        self.loaded.insert(name.to_string(), Arc::clone(&dictionary));
+        Ok(dictionary)
+    }
+}
@@ -0,0 +1,256 @@
+//! Statistics of the words each player found and missed across practice rounds, kept in a flat
+//! file, and drills which bring the missed words back on a spaced repetition schedule.
+//!
+//! Each word is in one of a few boxes. A word missed in a round, or answered wrongly in a
+//! drill, goes back to the first box and is due straight away, while each right answer moves it
+//! up a box, to be drilled again after a longer wait.
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use crate::trainer::Round;
+
+const DAY: u64 = 24 * 60 * 60;
+/// How long a word waits before being drilled again, for each box.
+const INTERVALS: [u64; 6] = [0, DAY, 3 * DAY, 7 * DAY, 14 * DAY, 30 * DAY];
+/// The number of the best missed words of a round which are kept for drilling.
+const MISSED_PER_ROUND: usize = 20;
+/// The number of letters at the start or end of a word which make up a pattern.
+const PATTERN_LENS: [usize; 2] = [2, 3];
+
+/// What is known about one word for one player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordStats {
+    /// The number of rounds in which the word was found.
+    pub found: u32,
+    /// The number of rounds in which the word was in the board but not found.
+    pub missed: u32,
+    /// The best score the word has had in a board.
+    pub best_score: u16,
+    /// The box of the word: 0 when it has just been missed, and higher for each right answer.
+    pub level: u8,
+    /// When the word is next due for a drill, in seconds since the Unix epoch.
+    pub due: u64,
+}
+
+/// A word to be recalled in a drill, from a clue showing some of its letters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DrillItem {
+    pub word: String,
+    /// The word with the letters to be recalled replaced by ?, eg. H???ING.
+    pub clue: String,
+    pub best_score: u16,
+}
+
+/// The statistics of every player, keyed by player and word.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    words: BTreeMap<(String, String), WordStats>,
+}
+
+/// Returns the start and end patterns of word, as globs such as RE* or *ING.
+fn patterns(word: &str) -> Vec<String> {
+    let letters: Vec<char> = word.chars().collect();
+    PATTERN_LENS.iter()
+        .filter(|&&len| letters.len() > len)
+        .flat_map(|&len| {
+            let start: String = letters[..len].iter().collect();
+            let end: String = letters[letters.len() - len..].iter().collect();
+            [format!("{}*", start), format!("*{}", end)]
+        })
+        .collect()
+}
+
+/// Checks that name can be written to the statistics file, which separates its fields with tabs
+/// and its lines with newlines.
+pub fn check_player_name(name: &str) -> Result<(), String> {
+    if name.contains(['\t', '\n', '\r']) {
+        return Err(format!("The player name {:?} can not contain tabs or line breaks.", name));
+    }
+    Ok(())
+}
+
+impl PlayerStats {
+    pub fn new() -> PlayerStats {
+        PlayerStats::default()
+    }
+
+    /// Reads the statistics file at path. A missing file has no statistics. The file is tab
+    /// separated, with a line for each player and word giving the times it was found and
+    /// missed, its best score, its box and when it is due.
+    pub fn read(path: &str) -> Result<PlayerStats, String> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(PlayerStats::new()),
+            Err(err) => return Err(format!("The statistics file {} could not be read: {}", path, err)),
+        };
+
+        let mut stats = PlayerStats::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|err| format!("The statistics file {} could not be read: {}", path, err))?;
+            let invalid = || format!("Line {} of the statistics file {} is not a word.", index + 1, path);
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 7 {
+                return Err(invalid());
+            }
+            let parse = |field: &str| field.parse::<u64>().map_err(|_| invalid());
+            let word_stats = WordStats {
+                found: parse(fields[2])? as u32,
+                missed: parse(fields[3])? as u32,
+                best_score: parse(fields[4])? as u16,
+                level: (parse(fields[5])? as usize).min(INTERVALS.len() - 1) as u8,
+                due: parse(fields[6])?,
+            };
+            stats.words.insert((fields[0].to_string(), fields[1].to_string()), word_stats);
+        }
+        Ok(stats)
+    }
+
+    /// Writes the statistics to the file at path, replacing it only once it has been written in
+    /// full.
+    pub fn write(&self, path: &str) -> Result<(), String> {
+        for (player, _) in self.words.keys() {
+            check_player_name(player)?;
+        }
+        let partial = format!("{}.partial", path);
+        let write = || -> io::Result<()> {
+            let mut file = io::BufWriter::new(File::create(&partial)?);
+            for ((player, word), stats) in &self.words {
+                writeln!(file, "{}\t{}\t{}\t{}\t{}\t{}\t{}", player, word, stats.found, stats.missed,
+                         stats.best_score, stats.level, stats.due)?;
+            }
+            file.flush()?;
+            fs::rename(&partial, path)
+        };
+        write().map_err(|err| format!("The statistics file {} could not be written: {}", path, err))
+    }
+
+    pub fn get(&self, player: &str, word: &str) -> Option<&WordStats> {
+        self.words.get(&(player.to_string(), word.to_string()))
+    }
+
+    /// Returns the statistics of word for player, starting them if the word is new.
+    fn entry(&mut self, player: &str, word: &str, now: u64) -> &mut WordStats {
+        self.words.entry((player.to_string(), word.to_string()))
+            .or_insert(WordStats { found: 0, missed: 0, best_score: 0, level: 0, due: now })
+    }
+
+    /// Records a finished round. Every found word counts as found, and the best missed words
+    /// count as missed, going back to the first box to be drilled from now on.
+    pub fn record_round(&mut self, player: &str, round: &Round, now: u64) {
+        let mut missed: Vec<(&str, u16)> = round.words().filter(|(word, _)| !round.is_found(word)).collect();
+        missed.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        for (word, score) in round.found() {
+            let stats = self.entry(player, word, now);
+            stats.found += 1;
+            stats.best_score = stats.best_score.max(*score);
+        }
+        for &(word, score) in missed.iter().take(MISSED_PER_ROUND) {
+            let stats = self.entry(player, word, now);
+            stats.missed += 1;
+            stats.best_score = stats.best_score.max(score);
+            stats.level = 0;
+            stats.due = now;
+        }
+    }
+
+    /// Records the answer to a drill of word: a right answer moves it up a box, and a wrong one
+    /// back to the first.
+    pub fn answer(&mut self, player: &str, word: &str, right: bool, now: u64) {
+        if let Some(stats) = self.words.get_mut(&(player.to_string(), word.to_string())) {
+            stats.level = if right { (stats.level + 1).min(INTERVALS.len() as u8 - 1) } else { 0 };
+            stats.due = now + INTERVALS[stats.level as usize];
+        }
+    }
+
+    /// Returns the start and end patterns, such as RE* or *ING, which the player misses most,
+    /// with the number of times words with the pattern were missed and found. Only patterns
+    /// missed at least min_missed times are listed, most missed first.
+    pub fn missed_patterns(&self, player: &str, min_missed: u32) -> Vec<(String, u32, u32)> {
+        let mut counts: HashMap<String, (u32, u32)> = HashMap::new();
+        for ((_, word), stats) in self.words.range((player.to_string(), String::new())..)
+            .take_while(|((owner, _), _)| owner == player) {
+            for pattern in patterns(word) {
+                let count = counts.entry(pattern).or_default();
+                count.0 += stats.missed;
+                count.1 += stats.found;
+            }
+        }
+
+        let mut patterns: Vec<(String, u32, u32)> = counts.into_iter()
+            .filter(|&(_, (missed, _))| missed >= min_missed)
+            .map(|(pattern, (missed, found))| (pattern, missed, found))
+            .collect();
+        patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.2.cmp(&b.2)).then_with(|| a.0.cmp(&b.0)));
+        patterns
+    }
+
+    /// Picks at most size words which are due for the player, those most worth drilling first:
+    /// the words scoring the most points, weighted by how often they were missed and by how
+    /// often the player misses their patterns. Each clue shows the word's first letter, along
+    /// with its most missed pattern, if it has one.
+    pub fn drill(&self, player: &str, now: u64, size: usize) -> Vec<DrillItem> {
+        let pattern_misses: HashMap<String, u32> = self.missed_patterns(player, 2).into_iter()
+            .map(|(pattern, missed, _)| (pattern, missed))
+            .collect();
+
+        let mut due: Vec<(u64, DrillItem)> = self.words.range((player.to_string(), String::new())..)
+            .take_while(|((owner, _), _)| owner == player)
+            .filter(|(_, stats)| stats.missed > 0 && stats.due <= now)
+            .map(|((_, word), stats)| {
+                let pattern = patterns(word).into_iter()
+                    .filter_map(|pattern| pattern_misses.get(&pattern).map(|&missed| (missed, pattern)))
+                    .max();
+                let weight = stats.best_score as u64 * stats.missed as u64
+                    * (1 + pattern.as_ref().map_or(0, |&(missed, _)| missed as u64));
+                let item = DrillItem {
+                    word: word.clone(),
+                    clue: clue(word, pattern.map(|(_, pattern)| pattern).as_deref()),
+                    best_score: stats.best_score,
+                };
+                (weight, item)
+            })
+            .collect();
+        due.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.word.cmp(&b.1.word)));
+        due.into_iter().take(size).map(|(_, item)| item).collect()
+    }
+}
+
+/// Hides the letters of word with ?, apart from the first letter and the letters of pattern.
+fn clue(word: &str, pattern: Option<&str>) -> String {
+    let letters: Vec<char> = word.chars().collect();
+    let shown = |index: usize| match pattern {
+        Some(pattern) if pattern.starts_with('*') => index >= letters.len() - (pattern.chars().count() - 1),
+        Some(pattern) => index < pattern.chars().count() - 1,
+        None => false,
+    };
+    letters.iter().enumerate()
+        .map(|(index, &letter)| if index == 0 || shown(index) { letter } else { '?' })
+        .collect()
+}
+
+/// Drills the player on the given words: each clue is shown, and the answer read from input is
+/// checked and recorded. Returns the number of right answers.
+pub fn run_drill<R: BufRead, W: Write>(stats: &mut PlayerStats, player: &str, items: &[DrillItem], now: u64,
+                                       input: R, out: &mut W) -> io::Result<usize> {
+    let mut lines = input.lines();
+    let mut right = 0;
+    for (index, item) in items.iter().enumerate() {
+        write!(out, "{}/{}: {} ({} letters, {} points)> ", index + 1, items.len(), item.clue,
+               item.word.chars().count(), item.best_score)?;
+        out.flush()?;
+        let Some(answer) = lines.next() else {
+            break;
+        };
+        let is_right = answer?.trim().to_uppercase() == item.word;
+        stats.answer(player, &item.word, is_right, now);
+        if is_right {
+            right += 1;
+            writeln!(out, "Right.")?;
+        } else {
+            writeln!(out, "It was {}.", item.word)?;
+        }
+    }
+    writeln!(out, "{} of {} right.", right, items.len())?;
+    Ok(right)
+}
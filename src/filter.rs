@@ -0,0 +1,288 @@
+//! Filters which pick out the solutions answering a question, such as "words of 6 or more
+//! letters which use the triple word tile", built either in code or from a short expression.
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use crate::{Board, BOARD_SIZE, path_to_vec};
+
+/// A multiplier tile which a word can be required to use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Multiplier {
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+
+impl Multiplier {
+    /// Reads a multiplier from its usual abbreviation: DL, TL, DW or TW.
+    pub fn from_name(name: &str) -> Option<Multiplier> {
+        match name.to_ascii_uppercase().as_str() {
+            "DL" => Some(Multiplier::DoubleLetter),
+            "TL" => Some(Multiplier::TripleLetter),
+            "DW" => Some(Multiplier::DoubleWord),
+            "TW" => Some(Multiplier::TripleWord),
+            _ => None,
+        }
+    }
+
+    /// Whether the tile at vertex of board carries this multiplier.
+    fn is_on(self, board: &Board, vertex: usize) -> bool {
+        match self {
+            Multiplier::DoubleLetter => board.letter_mults[vertex] == 2,
+            Multiplier::TripleLetter => board.letter_mults[vertex] == 3,
+            Multiplier::DoubleWord => board.word_int_mults[vertex] == 2,
+            Multiplier::TripleWord => board.word_int_mults[vertex] == 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum GlobToken {
+    /// * matches any number of letters.
+    Any,
+    /// ? matches a single letter.
+    One,
+    /// [ABC] matches one of the letters, and [!ABC] any other letter.
+    Class(Vec<char>, bool),
+    Letter(char),
+}
+
+/// A glob pattern over the letters of a word, eg. *ING or ?A[!S]*.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Glob {
+    tokens: Vec<GlobToken>,
+}
+
+impl Glob {
+    /// Parses a pattern, in which letters are matched regardless of case.
+    pub fn new(pattern: &str) -> Result<Glob, String> {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars().flat_map(char::to_uppercase);
+
+        while let Some(c) = chars.next() {
+            tokens.push(match c {
+                '*' => GlobToken::Any,
+                '?' => GlobToken::One,
+                '[' => {
+                    let mut letters = Vec::new();
+                    let mut negated = false;
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some('!') if letters.is_empty() && !negated => negated = true,
+                            Some(letter) => letters.push(letter),
+                            None => return Err(format!("The pattern {} has an unclosed [.", pattern)),
+                        }
+                    }
+                    GlobToken::Class(letters, negated)
+                }
+                letter => GlobToken::Letter(letter),
+            });
+        }
+
+        Ok(Glob { tokens })
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        let letters: Vec<char> = word.chars().collect();
+        Glob::matches_from(&self.tokens, &letters)
+    }
+
+    fn matches_from(tokens: &[GlobToken], letters: &[char]) -> bool {
+        match tokens.split_first() {
+            None => letters.is_empty(),
+            Some((GlobToken::Any, rest)) =>
+                (0..=letters.len()).any(|skip| Glob::matches_from(rest, &letters[skip..])),
+            Some((token, rest)) => match letters.split_first() {
+                None => false,
+                Some((&letter, remaining)) => {
+                    let matched = match token {
+                        GlobToken::One => true,
+                        GlobToken::Class(class, negated) => class.contains(&letter) != *negated,
+                        GlobToken::Letter(expected) => *expected == letter,
+                        GlobToken::Any => unreachable!(),
+                    };
+                    matched && Glob::matches_from(rest, remaining)
+                }
+            },
+        }
+    }
+}
+
+/// Keeps the solutions meeting every one of its conditions. A new filter keeps everything.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    len: RangeInclusive<u8>,
+    score: RangeInclusive<u16>,
+    patterns: Vec<Glob>,
+    // Bit i is set if the vertex i must be used, or must be avoided.
+    uses: u16,
+    avoids: u16,
+    multipliers: Vec<Multiplier>,
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter {
+            len: 0..=u8::MAX,
+            score: 0..=u16::MAX,
+            patterns: Vec::new(),
+            uses: 0,
+            avoids: 0,
+            multipliers: Vec::new(),
+        }
+    }
+}
+
+/// Returns the vertex of the cell in row x and column y.
+fn vertex((x, y): (u8, u8)) -> usize {
+    x as usize * BOARD_SIZE + y as usize
+}
+
+impl Filter {
+    pub fn new() -> Filter {
+        Filter::default()
+    }
+
+    pub fn min_len(mut self, min_len: u8) -> Filter {
+        self.len = min_len..=*self.len.end();
+        self
+    }
+
+    pub fn max_len(mut self, max_len: u8) -> Filter {
+        self.len = *self.len.start()..=max_len;
+        self
+    }
+
+    pub fn min_score(mut self, min_score: u16) -> Filter {
+        self.score = min_score..=*self.score.end();
+        self
+    }
+
+    pub fn max_score(mut self, max_score: u16) -> Filter {
+        self.score = *self.score.start()..=max_score;
+        self
+    }
+
+    /// Keeps the words matching a glob pattern.
+    pub fn pattern(mut self, glob: Glob) -> Filter {
+        self.patterns.push(glob);
+        self
+    }
+
+    /// Keeps the words whose path goes through the cell, given as (row, column).
+    pub fn uses_cell(mut self, cell: (u8, u8)) -> Filter {
+        self.uses |= 1 << vertex(cell);
+        self
+    }
+
+    /// Keeps the words whose path does not go through the cell, given as (row, column).
+    pub fn avoids_cell(mut self, cell: (u8, u8)) -> Filter {
+        self.avoids |= 1 << vertex(cell);
+        self
+    }
+
+    /// Keeps the words whose path goes through a tile with the multiplier.
+    pub fn uses_multiplier(mut self, multiplier: Multiplier) -> Filter {
+        self.multipliers.push(multiplier);
+        self
+    }
+
+    /// Whether the solution found in board meets every condition of the filter.
+    pub fn matches(&self, board: &Board, word: &str, score: u16, path: u64) -> bool {
+        let cells = path_to_vec(path);
+        let visited = cells.iter().fold(0u16, |visited, &cell| visited | 1 << vertex(cell));
+
+        self.len.contains(&(cells.len() as u8))
+            && self.score.contains(&score)
+            && visited & self.uses == self.uses
+            && visited & self.avoids == 0
+            && self.patterns.iter().all(|glob| glob.matches(word))
+            && self.multipliers.iter().all(|&multiplier| {
+                cells.iter().any(|&cell| multiplier.is_on(board, vertex(cell)))
+            })
+    }
+}
+
+/// Parses the range of numbers given by a comparison: =N, =MIN..MAX (where either end may be
+/// left out), >=N, >N, <=N or <N.
+fn parse_range(comparison: &str, value: &str, max: u16) -> Option<RangeInclusive<u16>> {
+    let parse = |value: &str| value.parse::<u16>().ok().filter(|&value| value <= max);
+    match comparison {
+        "=" => match value.split_once("..") {
+            Some((start, end)) => {
+                let start = if start.is_empty() { 0 } else { parse(start)? };
+                let end = if end.is_empty() { max } else { parse(end)? };
+                Some(start..=end)
+            }
+            None => parse(value).map(|value| value..=value),
+        },
+        ">=" => Some(parse(value)?..=max),
+        ">" => Some(parse(value)?.checked_add(1).filter(|&start| start <= max)?..=max),
+        "<=" => Some(0..=parse(value)?),
+        "<" => Some(0..=parse(value)?.checked_sub(1)?),
+        _ => None,
+    }
+}
+
+/// Splits a condition into its key, its comparison (=, >=, >, <= or <) and its value.
+fn split_condition(condition: &str) -> Option<(&str, &str, &str)> {
+    let start = condition.find(['=', '<', '>'])?;
+    let len = if condition[start..].starts_with(">=") || condition[start..].starts_with("<=") { 2 } else { 1 };
+    Some((&condition[..start], &condition[start..start + len], &condition[start + len..]))
+}
+
+/// Parses a cell written as ROW,COLUMN.
+fn parse_cell(value: &str) -> Option<(u8, u8)> {
+    let (x, y) = value.split_once(',')?;
+    let cell = (x.trim().parse().ok()?, y.trim().parse().ok()?);
+    if cell.0 as usize >= BOARD_SIZE || cell.1 as usize >= BOARD_SIZE {
+        return None;
+    }
+    Some(cell)
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    /// Parses a filter from space separated conditions, all of which must hold:
+    ///
+    /// - `len=6..` (or `len=4`, `len=..8`, `len=4..8`, `len>=6`, `len<8`) for the number of letters,
+    /// - `score=40..` for the score, with ranges written in the same way,
+    /// - `word=*ING` for a glob pattern on the word, using *, ? and [ABC] or [!ABC],
+    /// - `use=0,0` and `avoid=0,0` for a cell, given as ROW,COLUMN, which the path must use or avoid,
+    /// - `mult=TW` for a multiplier tile (DL, TL, DW or TW) which the path must use.
+    fn from_str(expression: &str) -> Result<Filter, String> {
+        let mut filter = Filter::new();
+        for condition in expression.split_whitespace() {
+            let invalid = || format!("\"{}\" is not a filter condition.", condition);
+            let (key, comparison, value) = split_condition(condition).ok_or_else(invalid)?;
+            filter = match (key, comparison) {
+                ("len", _) => {
+                    let len = parse_range(comparison, value, u8::MAX as u16).ok_or_else(invalid)?;
+                    filter.min_len(*len.start() as u8).max_len(*len.end() as u8)
+                }
+                ("score", _) => {
+                    let score = parse_range(comparison, value, u16::MAX).ok_or_else(invalid)?;
+                    filter.min_score(*score.start()).max_score(*score.end())
+                }
+                ("word", "=") => filter.pattern(Glob::new(value)?),
+                ("use", "=") => filter.uses_cell(parse_cell(value).ok_or_else(invalid)?),
+                ("avoid", "=") => filter.avoids_cell(parse_cell(value).ok_or_else(invalid)?),
+                ("mult", "=") => filter.uses_multiplier(Multiplier::from_name(value).ok_or_else(invalid)?),
+                _ => return Err(invalid()),
+            };
+        }
+        Ok(filter)
+    }
+}
+
+impl Board {
+    /// Keeps only the found words which match filter.
+    pub fn filter_entries(&mut self, filter: &Filter) {
+        let word_info = std::mem::take(&mut self.word_info);
+        self.word_info = word_info.into_iter()
+            .filter(|(word, score, path)| filter.matches(self, word, *score, *path))
+            .collect();
+    }
+}
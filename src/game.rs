@@ -0,0 +1,208 @@
+//! Full matches: three rounds, each on its own board, with multipliers added in the later
+//! rounds. Every player plays every board, and the player with the most points over the three
+//! rounds wins.
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::Board;
+use crate::compare::{Comparison, compare};
+use crate::dictionary::Dictionary;
+use crate::random::{MultiplierCounts, Rng, random_board};
+use crate::trainer::Guess;
+
+pub const ROUNDS: usize = 3;
+
+/// The multipliers of the boards of each round: none in the first, letter multipliers in the
+/// second, and word multipliers as well in the third.
+pub const ROUND_MULTIPLIERS: [MultiplierCounts; ROUNDS] = [
+    MultiplierCounts::NONE,
+    MultiplierCounts { double_letter: 2, triple_letter: 1, double_word: 0, triple_word: 0 },
+    MultiplierCounts { double_letter: 2, triple_letter: 1, double_word: 1, triple_word: 1 },
+];
+
+/// A match between players, played one round at a time.
+pub struct Match {
+    players: Vec<String>,
+    boards: Vec<Board>,
+    // The best score of each distinct word of each board.
+    scores: Vec<HashMap<String, u16>>,
+    // The words found by each player in each round, in the order they were entered.
+    found: Vec<Vec<Vec<(String, u16)>>>,
+    round: usize,
+}
+
+impl Match {
+    /// Starts a match on the given boards, one for each round, solving them.
+    pub fn new(players: &[&str], boards: Vec<Board>) -> Result<Match, String> {
+        if players.is_empty() {
+            return Err("A match needs at least one player.".to_string());
+        }
+        if boards.len() != ROUNDS {
+            return Err(format!("A match needs {} boards, one for each round, not {}.", ROUNDS, boards.len()));
+        }
+
+        let mut boards = boards;
+        let scores = boards.iter_mut()
+            .map(|board| {
+                board.solve();
+                board.dedup_entries();
+                board.word_info.iter().map(|(word, score, _)| (word.clone(), *score)).collect()
+            })
+            .collect();
+        Ok(Match {
+            players: players.iter().map(|player| player.to_string()).collect(),
+            boards,
+            scores,
+            found: vec![vec![Vec::new(); players.len()]; ROUNDS],
+            round: 0,
+        })
+    }
+
+    /// Starts a match on random boards drawn from seed, with the multipliers of each round
+    /// given by ROUND_MULTIPLIERS.
+    pub fn generate(players: &[&str], dictionary: &Arc<Dictionary>, seed: u64) -> Result<Match, String> {
+        let mut rng = Rng::new(seed);
+        let boards = ROUND_MULTIPLIERS.iter()
+            .map(|counts| {
+                let (letters, word_int_mults, letter_mults) = random_board(&dictionary.alphabet, counts, &mut rng);
+                Board::new(letters, word_int_mults, letter_mults, Arc::clone(dictionary))
+            })
+            .collect();
+        Match::new(players, boards)
+    }
+
+    pub fn players(&self) -> &[String] {
+        &self.players
+    }
+
+    /// The round being played, from 0, or ROUNDS once the match is over.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.round == ROUNDS
+    }
+
+    /// The solved board of a round, with each word found once, best first.
+    pub fn board(&self, round: usize) -> &Board {
+        &self.boards[round]
+    }
+
+    /// Enters a word for player in the current round. A word scores only the first time the
+    /// player enters it in a round, and no word scores once the match is over.
+    pub fn guess(&mut self, player: usize, word: &str) -> Guess {
+        if self.is_over() {
+            return Guess::NotInBoard;
+        }
+        let word = word.trim().to_uppercase();
+        let board = &self.boards[self.round];
+        let found = &mut self.found[self.round][player];
+        match self.scores[self.round].get(&word) {
+            Some(_) if found.iter().any(|(found, _)| *found == word) => Guess::Repeated,
+            Some(&score) => {
+                found.push((word, score));
+                Guess::Scored(score)
+            }
+            None => match board.alphabet().encode(&word) {
+                Some(encoded) if board.dictionary.store.is_word(encoded) => Guess::NotInBoard,
+                _ => Guess::NotAWord,
+            },
+        }
+    }
+
+    /// The words found by player in round, with their scores.
+    pub fn found(&self, round: usize, player: usize) -> &[(String, u16)] {
+        &self.found[round][player]
+    }
+
+    /// The points scored by player in round.
+    pub fn round_score(&self, round: usize, player: usize) -> u32 {
+        self.found[round][player].iter().map(|(_, score)| *score as u32).sum()
+    }
+
+    /// Compares the words found by two players in round, listing the ten best words neither
+    /// found.
+    pub fn compare(&self, round: usize, a: usize, b: usize) -> Comparison {
+        let words = |player: usize| -> Vec<&str> { self.found[round][player].iter().map(|(word, _)| word.as_str()).collect() };
+        compare(&self.boards[round], (&self.players[a], &self.players[b]), &words(a), &words(b), 10)
+    }
+
+    /// Ends the current round, moving on to the next one.
+    pub fn end_round(&mut self) {
+        self.round = (self.round + 1).min(ROUNDS);
+    }
+
+    /// The scores so far, and the players leading the match.
+    pub fn result(&self) -> MatchResult {
+        let scores: Vec<[u32; ROUNDS]> = (0..self.players.len())
+            .map(|player| std::array::from_fn(|round| self.round_score(round, player)))
+            .collect();
+        let totals: Vec<u32> = scores.iter().map(|rounds| rounds.iter().sum()).collect();
+        let best = totals.iter().copied().max().unwrap_or(0);
+        MatchResult {
+            players: self.players.clone(),
+            winners: (0..totals.len()).filter(|&player| totals[player] == best).collect(),
+            scores,
+        }
+    }
+}
+
+/// The scores of a match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchResult {
+    pub players: Vec<String>,
+    /// The points of each player in each round.
+    pub scores: Vec<[u32; ROUNDS]>,
+    /// The players with the most points, more than one if they drew.
+    pub winners: Vec<usize>,
+}
+
+impl MatchResult {
+    pub fn total(&self, player: usize) -> u32 {
+        self.scores[player].iter().sum()
+    }
+
+    /// Writes a table of each player's points in each round, followed by the winner.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "{:<12} Round 1  Round 2  Round 3    Total", "Player")?;
+        for (player, name) in self.players.iter().enumerate() {
+            let [first, second, third] = self.scores[player];
+            writeln!(out, "{:<12} {:>7}  {:>7}  {:>7}  {:>7}", name, first, second, third, self.total(player))?;
+        }
+        let names: Vec<&str> = self.winners.iter().map(|&player| self.players[player].as_str()).collect();
+        match names.as_slice() {
+            [] => Ok(()),
+            [winner] => writeln!(out, "{} wins with {} points.", winner, self.total(self.winners[0])),
+            _ => writeln!(out, "{} draw with {} points.", names.join(" and "), self.total(self.winners[0])),
+        }
+    }
+}
+
+/// Plays the turn of player in the current round: words are read from input, one per line,
+/// until an empty line, the end of input, or a word typed after round_time has run out.
+pub fn play_turn<R: BufRead, W: Write>(game: &mut Match, player: usize, round_time: Duration, input: &mut R,
+                                       out: &mut W) -> io::Result<u32> {
+    writeln!(out, "{}, you have {}s. Type one word per line, and an empty line when you are done.",
+             game.players[player], round_time.as_secs_f32())?;
+    let start = Instant::now();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if start.elapsed() > round_time {
+            writeln!(out, "Time is up, so {} was not counted.", line.trim().to_uppercase())?;
+            break;
+        }
+        match game.guess(player, &line) {
+            Guess::Scored(score) => writeln!(out, "+{}", score)?,
+            Guess::Repeated => writeln!(out, "You already found that word.")?,
+            Guess::NotInBoard => writeln!(out, "That word is not in the board.")?,
+            Guess::NotAWord => writeln!(out, "That is not a word.")?,
+        }
+    }
+    Ok(game.round_score(game.round, player))
+}
@@ -0,0 +1,117 @@
+//! Exports words as the swipes which enter them on a device, either as JSON or as a shell
+//! script which replays the swipes.
+use std::io::{self, Write};
+use crate::metadata::{Metadata, json_string};
+use crate::path_to_vec;
+
+// Delay between each point of a swipe, in milliseconds.
+const STEP_DELAY_MS: u32 = 40;
+
+/// Where the board appears on the screen, in pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoardGeometry {
+    /// The centre of the top left cell.
+    pub origin: (u32, u32),
+    /// The distance between the centres of two neighbouring cells.
+    pub pitch: u32,
+}
+
+impl BoardGeometry {
+    /// Returns the pixel coordinates of the centre of the cell in row x and column y.
+    pub fn cell_centre(&self, (x, y): (u8, u8)) -> (u32, u32) {
+        (self.origin.0 + y as u32 * self.pitch, self.origin.1 + x as u32 * self.pitch)
+    }
+
+    /// Returns the pixel coordinates of each cell in the path, in order.
+    pub fn swipe_points(&self, path: u64) -> Vec<(u32, u32)> {
+        path_to_vec(path).into_iter().map(|cell| self.cell_centre(cell)).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GestureFormat {
+    /// A JSON array of words, each with its score and swipe points, along with any details of
+    /// the word given by the metadata.
+    Json,
+    /// A shell script of adb `input motionevent` commands, for Android devices. These are used
+    /// rather than `input swipe`, which can only swipe in a straight line between two points.
+    Adb,
+    /// A shell script of xdotool commands, for emulators and desktop versions.
+    Xdotool,
+}
+
+impl GestureFormat {
+    pub fn from_name(name: &str) -> Option<GestureFormat> {
+        match name {
+            "json" => Some(GestureFormat::Json),
+            "adb" => Some(GestureFormat::Adb),
+            "xdotool" => Some(GestureFormat::Xdotool),
+            _ => None,
+        }
+    }
+
+    /// The extension of files written in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            GestureFormat::Json => "json",
+            GestureFormat::Adb | GestureFormat::Xdotool => "sh",
+        }
+    }
+}
+
+/// Writes the swipes entering each word in words, in order, to out. Words are annotated with
+/// their details from metadata, if it is given and the format has room for them.
+pub fn write_gestures<W: Write>(out: &mut W, words: &[(String, u16, u64)], geometry: &BoardGeometry,
+                                format: GestureFormat, metadata: Option<&Metadata>) -> io::Result<()> {
+    match format {
+        GestureFormat::Json => write_json(out, words, geometry, metadata),
+        GestureFormat::Adb => write_script(out, words, geometry, |out, points| {
+            let ((x, y), rest) = points.split_first().unwrap();
+            writeln!(out, "adb shell input motionevent DOWN {} {}", x, y)?;
+            for (x, y) in rest {
+                writeln!(out, "sleep {}", STEP_DELAY_MS as f32 / 1000.)?;
+                writeln!(out, "adb shell input motionevent MOVE {} {}", x, y)?;
+            }
+            let (x, y) = points.last().unwrap();
+            writeln!(out, "adb shell input motionevent UP {} {}", x, y)
+        }),
+        GestureFormat::Xdotool => write_script(out, words, geometry, |out, points| {
+            let ((x, y), rest) = points.split_first().unwrap();
+            write!(out, "xdotool mousemove {} {} mousedown 1", x, y)?;
+            for (x, y) in rest {
+                write!(out, " sleep {} mousemove {} {}", STEP_DELAY_MS as f32 / 1000., x, y)?;
+            }
+            writeln!(out, " mouseup 1")
+        }),
+    }
+}
+
+fn write_json<W: Write>(out: &mut W, words: &[(String, u16, u64)], geometry: &BoardGeometry,
+                        metadata: Option<&Metadata>) -> io::Result<()> {
+    writeln!(out, "[")?;
+    for (index, (word, score, path)) in words.iter().enumerate() {
+        let points: Vec<String> = geometry.swipe_points(*path).iter()
+            .map(|(x, y)| format!("[{}, {}]", x, y))
+            .collect();
+        write!(out, "  {{\"word\": {}, \"score\": {}, \"points\": [{}]", json_string(word), score, points.join(", "))?;
+        if let Some(info) = metadata.and_then(|metadata| metadata.get(word)) {
+            info.write_json_fields(out)?;
+        }
+        let separator = if index + 1 == words.len() { "" } else { "," };
+        writeln!(out, "}}{}", separator)?;
+    }
+    writeln!(out, "]")
+}
+
+/// Writes a shell script with a comment naming each word, followed by the commands
+/// which write_swipe generates for the word's swipe points.
+fn write_script<W: Write, F>(out: &mut W, words: &[(String, u16, u64)], geometry: &BoardGeometry,
+                             write_swipe: F) -> io::Result<()>
+    where F: Fn(&mut W, &[(u32, u32)]) -> io::Result<()> {
+    writeln!(out, "#!/bin/sh")?;
+    for (word, score, path) in words {
+        writeln!(out, "# {} ({})", word, score)?;
+        write_swipe(out, &geometry.swipe_points(*path))?;
+    }
+    Ok(())
+}
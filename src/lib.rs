@@ -0,0 +1,611 @@
+use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::fs::File;
+use std::io::{self, BufReader, BufRead, Read, Write, BufWriter};
+use std::sync::Arc;
+use fnv::{FnvHashSet, FnvHasher};
+use std::hash::BuildHasherDefault;
+use unicode_normalization::UnicodeNormalization;
+use crate::alphabet::Alphabet;
+use crate::dictionary::{Dictionary, WordSet};
+use crate::metadata::{WordInfo, json_string};
+use crate::store::WordStore;
+
+/// Evaluates body with store bound to the store of dictionary, so that searches are compiled
+/// separately for each kind of store rather than dispatching on every step.
+macro_rules! with_store {
+    ($dictionary:expr, |$store:ident| $body:expr) => {
+        match &$dictionary.store {
+            $crate::dictionary::Store::Hashed($store) => $body,
+            $crate::dictionary::Store::Sorted($store) => $body,
+            $crate::dictionary::Store::Trie($store) => $body,
+            $crate::dictionary::Store::Fst($store) => $body,
+        }
+    };
+}
+
+pub mod alphabet;
+pub mod bound;
+pub mod compare;
+pub mod dictionary;
+pub mod drill;
+pub mod filter;
+pub mod game;
+pub mod general;
+pub mod gesture;
+pub mod mapped;
+pub mod metadata;
+pub mod order;
+pub mod plan;
+pub mod play;
+pub mod pretty;
+pub mod random;
+pub mod recursive;
+pub mod screenshot;
+pub mod simulate;
+pub mod store;
+pub mod stream;
+pub mod strategy;
+pub mod svg;
+pub mod trainer;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod transducer;
+
+// const PREFIXES: &'static [u8] = include_bytes!("../data/prefixes/binary.bin");
+// const DICT: &'static [u8] = include_bytes!("../data/TWL06/binary.bin");
+
+// const PATH_TO_PREFIXES: &str = r"data/prefixes/";
+// const PATH_TO_DICTIONARY: &str = r"data/";
+pub const PATH_TO_BOARD: &str = r"board.txt";
+
+pub const MIN_WORD_LEN: u8 = 2;
+pub const MAX_WORD_LEN: u8 = 12;
+
+pub const BOARD_SIZE: usize = 4;
+const BOARD_SIZE_I8: i8 = BOARD_SIZE as i8;
+pub const POINT_VALS: [u8; 27] = [0, 1, 4, 4, 2, 1, 4, 3, 4, 1, 10, 5, 1, 3, 1, 1, 4,
+    10, 1, 1, 1, 2, 4, 4, 8, 4, 8];
+
+// These options can be tweaked to improve performance if necessary.
+pub const PREFIX_LOWER_BOUND: u8 = 2;
+pub const PREFIX_UPPER_BOUND: u8 = 8;
+
+pub const U64_TO_CHAR: [char; 30] = ['!', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I',
+    'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '2', '3', '-'];
+
+// const U64_TO_U8: [u8; 30] = *b"!ABCDEFGHIJKLMNOPQRSTUVWXYZ23-";
+
+// const TWO_U64: u64 = 27;
+// const THREE_U64: u64 = 28;
+// const DASH_U64: u64 = 29;
+
+//const DICT_BYTES: &'static [u8] = include_bytes!("../data/TWL06/binary.bin");
+//const PREFIX_BYTES: &'static [u8] = include_bytes!("../data/prefixes/binary.bin");
+
+/// One value for each tile of the board, row by row.
+pub type Tiles = [u8; BOARD_SIZE * BOARD_SIZE];
+
+/// An entry on the stack of a depth first search:
+///                     path, word, state, word_pts, word_mult, word_len, visited
+pub(crate) type StackEntry<State> = (u64, u64, State, u16, u16, u8, u16);
+
+/// Returns the score of a word of word_len letters with word_pts points and a word multiplier of
+/// word_mult. Boards with many word multipliers can score more than a u16 holds, so the score
+/// saturates rather than overflowing.
+pub(crate) fn word_score(word_pts: u16, word_mult: u16, word_len: u8) -> u16 {
+    let bonus = if word_len > 4 { 5 * (word_len as u32 - 4) } else { 0 };
+    (word_pts as u32 * word_mult as u32 + bonus).min(u16::MAX as u32) as u16
+}
+
+/// Maintains the board state and words found in the board.
+pub struct Board {
+    //              word, score, path
+    pub word_info: Vec<(String, u16, u64)>,
+    pub board: [u8; BOARD_SIZE * BOARD_SIZE],
+    pub points: [u8; BOARD_SIZE * BOARD_SIZE],
+    pub word_int_mults: [u8; BOARD_SIZE * BOARD_SIZE],
+    pub letter_mults: [u8; BOARD_SIZE * BOARD_SIZE],
+    pub dictionary: Arc<Dictionary>,
+}
+
+impl Board {
+    /// Creates a board with no words found yet, computing the points of each letter. The board
+    /// must be encoded with the dictionary's alphabet.
+    pub fn new(board: [u8; BOARD_SIZE * BOARD_SIZE],
+               word_int_mults: [u8; BOARD_SIZE * BOARD_SIZE],
+               letter_mults: [u8; BOARD_SIZE * BOARD_SIZE],
+               dictionary: Arc<Dictionary>) -> Board {
+        Board {
+            word_info: Vec::with_capacity(500),
+            board,
+            points: get_points(&board, &letter_mults, &dictionary.alphabet),
+            word_int_mults,
+            letter_mults,
+            dictionary,
+        }
+    }
+
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.dictionary.alphabet
+    }
+
+    /// Returns the name of the dictionary layer a found word came from: the base dictionary,
+    /// or the allow list which added it.
+    pub fn word_layer(&self, word: &str) -> Option<&str> {
+        self.dictionary.layer_of(self.alphabet().encode(word)?)
+    }
+
+    /// Finds every word in the board, replacing any previously found words.
+    pub fn solve(&mut self) {
+        let dictionary = Arc::clone(&self.dictionary);
+        with_store!(dictionary, |store| self.solve_with(store))
+    }
+
+    /// Finds every word of store in the board, replacing any previously found words. The
+    /// store must use the same alphabet as the board's dictionary.
+    pub fn solve_with<S: WordStore>(&mut self, store: &S) {
+        self.word_info.clear();
+        dfs(self, store, gen_graph());
+    }
+
+    pub fn sort_entries(&mut self) {
+        self.word_info.sort_by_key(|entry| Reverse(entry.1))
+    }
+
+    /// Keeps only the highest scoring path for each word, leaving the entries sorted in
+    /// descending order of score.
+    pub fn dedup_entries(&mut self) {
+        self.sort_entries();
+        let mut seen = HashSet::with_capacity(self.word_info.len());
+        self.word_info.retain(|(word, _, _)| seen.insert(word.clone()));
+    }
+
+    /// Returns the details of a found word from the dictionary's metadata, if it has any.
+    pub fn word_metadata(&self, word: &str) -> Option<&WordInfo> {
+        self.dictionary.metadata()?.get(word)
+    }
+
+    /// Writes the found words to out as a JSON array, each with its score, its path as a list
+    /// of [row, column] cells, and any details of the word from the dictionary's metadata.
+    pub fn write_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "[")?;
+        for (index, (word, score, path)) in self.word_info.iter().enumerate() {
+            let cells: Vec<String> = path_to_vec(*path).iter()
+                .map(|(x, y)| format!("[{}, {}]", x, y))
+                .collect();
+            write!(out, "  {{\"word\": {}, \"score\": {}, \"path\": [{}]", json_string(word), score, cells.join(", "))?;
+            if let Some(info) = self.word_metadata(word) {
+                info.write_json_fields(out)?;
+            }
+            let separator = if index + 1 == self.word_info.len() { "" } else { "," };
+            writeln!(out, "}}{}", separator)?;
+        }
+        writeln!(out, "]")
+    }
+
+    pub fn write_to_file(&self) {
+        let file = File::create("./words.txt").unwrap();
+        let mut buf_writer = BufWriter::with_capacity(24 * 1024, file);
+        self.write_words(&mut buf_writer).unwrap();
+        buf_writer.flush().unwrap();
+    }
+
+    /// Writes each found word to out on a line of its own, with its score and path, as in
+    /// words.txt.
+    pub fn write_words<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut path_buf = *b"(0, 0), (0, 0), (0, 0), (0, 0), \
+        (0, 0), (0, 0), (0, 0), (0, 0), \
+        (0, 0), (0, 0), (0, 0), (0, 0), ";
+        let mut path_buf_ = [(0, 0); 12];
+        for (word, score, path_as_u64) in self.word_info.iter() {
+            // assert!(path.len() * 8 <= path_buf.len());
+            let max_bit = path_to_vec_buffered(*path_as_u64, &mut path_buf_);
+            for (i, &(x, y)) in path_buf_[max_bit..11].iter().enumerate() {
+                path_buf[i * 8 + 1] = x + 48;
+                path_buf[i * 8 + 4] = y + 48;
+            }
+            let (x, y) = path_buf_[11];
+            let i = 11 - max_bit;
+            path_buf[i * 8 + 1] = x + 48;
+            path_buf[i * 8 + 4] = y + 48;
+            path_buf[i * 8 + 6] = b']';
+            path_buf[i * 8 + 7] = b'\n';
+
+            out.write_all(format!("{}, {}, [", word, *score, ).as_bytes())?;
+            out.write_all(&path_buf[..i * 8 + 8])?;
+            path_buf[i * 8 + 6] = b',';
+            path_buf[i * 8 + 7] = b' ';
+        }
+        Ok(())
+    }
+}
+
+/// A non recursive depth first search which identifies all words in store, and adds them to
+/// board.word_info_as_str with their string representation, score and path. Returns nothing.
+pub fn dfs<S: WordStore>(board: &mut Board, store: &S, graph: Vec<Vec<u8>>) {
+    let dictionary = Arc::clone(&board.dictionary);
+    let bits = dictionary.alphabet.bits_per_letter();
+    let max_word_len = dictionary.alphabet.max_word_len();
+    let mut stack: Vec<StackEntry<S::State>> = Vec::with_capacity(120);
+    for i in 0..BOARD_SIZE * BOARD_SIZE {
+        if let Some(state) = store.step(store.start(), board.board[i]) {
+            stack.push(((0b10000 | i) as u64,
+            board.board[i] as u64,
+            state,
+            board.points[i] as u16,
+            board.word_int_mults[i] as u16,
+            1, 0));
+        }
+    }
+    // Paths consist of 12 five bit vertices:
+    // [continuation_flag:1][x:2][y:2]
+
+    // This whole thing takes about 100ns per iteration, on average (0.0006s for 6000 fn calls).
+    // Out of 72846 values, we narrow it down to 6000 -> produce 410 results.
+    // Most pruning occurs around 4-8 values. 2-3 doesn't really do much, but the cost of hashing
+    // is roughly equal to the cost of going through a full operation. Past 9 values, most of the tree
+    // is already completed.
+    while let Some((path, word, state, word_pts, word_mult, mut word_len, mut visited)) = stack.pop() {
+        if (MIN_WORD_LEN..=max_word_len).contains(&word_len) && store.ends_word(state) {
+            let score = word_score(word_pts, word_mult, word_len);
+
+            // Parsing words takes very little time - only ~3% of calls get this far.
+            board.word_info.push((dictionary.alphabet.decode(word), score, path));
+        }
+
+        let vert = path & 0xF;
+        visited |= 1 << vert;
+        word_len += 1;
+
+        for &vertex in &graph[vert as usize] {
+            if ((visited >> vertex) & 1) == 0 {
+                let temp_word = (word << bits) | (board.board[vertex as usize] as u64);
+
+                // Testing bloom filters doesn't really suggest a significant difference.
+                let temp_state = match store.step(state, board.board[vertex as usize]) {
+                    Some(temp_state) => temp_state,
+                    None => continue,
+                };
+
+                let path_clone = (path << 5) | 0b10000 | (vertex as u64);
+
+                if word_len == max_word_len {
+                    if store.ends_word(temp_state) {
+                        let word_pts = word_pts + board.points[vertex as usize] as u16;
+                        let word_mult = word_mult.saturating_mul(board.word_int_mults[vertex as usize] as u16);
+                        let score = word_score(word_pts, word_mult, max_word_len);
+                        board.word_info.push((dictionary.alphabet.decode(temp_word),
+                                              score, path_clone));
+                    }
+                    continue;
+                }
+
+                stack.push((path_clone, temp_word, temp_state, word_pts + board.points[vertex as usize] as u16,
+                            word_mult.saturating_mul(board.word_int_mults[vertex as usize] as u16), word_len,
+                            visited | (1 << vertex)));
+            }
+        }
+    }
+}
+
+
+// /// Returns a u64 representation of any string that is twelve characters or less, and only
+// /// contains the letters A-Z. Every five bits, up until 60 bits or five zero bits occur,
+// /// correspond to the letter in U64_TO_CHAR with the same index (eg. as usize).
+// fn string_to_u64(string_to_convert: &String, str_to_u64: &HashMap<char, u8>) -> u64 {
+//     let mut output: u64 = 0;
+//     for c_u64 in string_to_convert.chars().map(|c| str_to_u64[&c]) {
+//         output = (output << 5) | (c_u64 as u64);
+//     }
+//     return output;
+// }
+
+/// Generates the string representation of any string that is represented in the first 60
+/// bits of str_as_num, where each group of five consecutive bits corresponds the the character
+/// at the index in U64_TO_CHAR.
+pub fn parse_to_str(str_as_num: u64) -> String {
+    let mut str_repr = [U64_TO_CHAR[0]; 12];
+    let mut str_numbers = str_as_num;
+    let mut max_bit = 12;
+
+    for _ in 0..12 {
+        // Read the last five bits.
+        let val = (str_numbers & 0b11111) as usize;
+        if val != 0 {
+            max_bit -= 1;
+            str_numbers >>= 5;
+            str_repr[max_bit] = U64_TO_CHAR[val];
+        } else {
+            break;
+        }
+    }
+
+    // unsafe {
+    //     return std::str::from_utf8_unchecked(&str_repr[max_bit..]).to_string();
+    // }
+    str_repr[max_bit..].iter().collect()
+}
+
+pub fn path_to_vec_buffered(path_as_u64: u64, buf: &mut [(u8, u8); 12]) -> usize {
+    let mut mut_path = path_as_u64;
+    let mut max_bit = 12;
+
+    while mut_path & 0b10000 == 0b10000 {
+        let y = (mut_path & 0b11) as u8;
+        mut_path >>= 2;
+        let x = (mut_path & 0b11) as u8;
+        mut_path >>= 3;
+        max_bit -= 1;
+        buf[max_bit] = (x, y);
+    }
+
+    max_bit
+}
+
+pub fn path_to_vec(path_as_u64: u64) -> Vec<(u8, u8)> {
+    let mut path_repr = [(0, 0); 12];
+
+    let mut mut_path = path_as_u64;
+    let mut max_bit = 12;
+
+    while mut_path & 0b10000 == 0b10000 {
+        let y = (mut_path & 0b11) as u8;
+        mut_path >>= 2;
+        let x = (mut_path & 0b11) as u8;
+        mut_path >>= 3;
+        max_bit -= 1;
+        path_repr[max_bit] = (x, y);
+    }
+
+    path_repr[max_bit..].to_vec()
+}
+
+/// Generates the u64 representation of a path of (x, y) cells, the inverse of path_to_vec.
+pub fn vec_to_path(path: &[(u8, u8)]) -> u64 {
+    path.iter().fold(0, |path_as_u64, &(x, y)| (path_as_u64 << 5) | 0b10000 | ((x << 2) | y) as u64)
+}
+
+
+// /// Returns a HashSet containing all prefixes of length PREFIX_LOWER_BOUND to PREFIX_UPPER_BOUND
+// /// in their u64 representation.
+// fn read_prefixes(str_to_u64: &HashMap<char, u8>) -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
+//     let mut prefixes = FnvHashSet::with_capacity_and_hasher(250000, Default::default());
+//     let file = File::open("./data/prefixes/prefixes.txt").unwrap();
+//
+//     let mut reader = BufReader::new(file);
+//     let mut s = String::new();
+//
+//     while let res = reader.read_line(&mut s).expect("Reading prefixes failed.") {
+//         if res == 0 {
+//             break;
+//         }
+//         prefixes.insert(string_to_u64(&s[..res - 2].to_owned(), str_to_u64));
+//     }
+//     return prefixes;
+// }
+
+/// Reads files which have been preprocessed to be compressed string representations.
+pub fn read_binary_prefixes() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
+    let mut prefixes = FnvHashSet::with_capacity_and_hasher(275944 + 1, Default::default());
+    let file = File::open("./data/prefixes/binary.bin").unwrap();
+
+    let mut reader = BufReader::new(file);
+    let mut s = [0; 8];
+
+    while reader.read(&mut s).expect("Reading binary prefix file failed.") == 8 {
+        prefixes.insert(u64::from_be_bytes(s));
+    }
+
+    prefixes
+}
+
+
+// fn parse_prefixes() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
+//     assert_eq!(PREFIXES.len() & 0b111, 0);
+//     let mut prefixes = FnvHashSet::with_capacity_and_hasher(275944 + 1, Default::default());
+//
+//     let mut s = [0u8; 8];
+//
+//     for i in 0..PREFIXES.len() / 8 {
+//         s.clone_from_slice(&PREFIXES[i*8..i*8+8]);
+//         prefixes.insert(u64::from_be_bytes(s));
+//     }
+//
+//     return prefixes;
+// }
+//
+
+// /// Returns a HashSet containing all words in their u64 representation.
+// fn read_dict(str_to_u64: &HashMap<char, u8>) -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
+//     let mut dict = FnvHashSet::with_capacity_and_hasher(200000, Default::default());
+//     let file = File::open("./data/TWL06Trimmed.txt").unwrap();
+//
+//     let mut reader = BufReader::new(file);
+//     let mut s = String::new();
+//
+//     while let res = reader.read_line(&mut s).expect("Reading dictionary failed.") {
+//         if res == 0 {
+//             break;
+//         }
+//         dict.insert(string_to_u64(&s[..res - 2].to_owned(), str_to_u64));
+//     }
+//
+//     return dict;
+// }
+
+/// Reads a file of packed words (or prefixes), each stored as a big endian u64.
+pub fn read_binary_words(file_path: &str) -> io::Result<WordSet> {
+    let file = File::open(file_path)?;
+    let mut words = FnvHashSet::with_capacity_and_hasher(file.metadata()?.len() as usize / 8, Default::default());
+    let mut reader = BufReader::new(file);
+
+    let mut s = [0; 8];
+    while reader.read(&mut s)? == 8 {
+        words.insert(u64::from_be_bytes(s));
+    }
+
+    Ok(words)
+}
+
+pub fn read_binary_dict() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
+    let mut dict = FnvHashSet::with_capacity_and_hasher(162725 + 1, Default::default());
+    let file = File::open("./data/TWL06/binary.bin").unwrap();
+    let mut reader = BufReader::new(file);
+
+    let mut s = [0; 8];
+    while reader.read(&mut s).expect("Reading binary dictionary failed.") == 8 {
+        dict.insert(u64::from_be_bytes(s));
+    }
+
+    dict
+}
+
+// fn parse_dict() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
+//     assert_eq!(DICT.len() & 0b111, 0);
+//     let mut dict = FnvHashSet::with_capacity_and_hasher(162725 + 1, Default::default());
+//     let mut s = [0u8; 8];
+//
+//     for i in 0..DICT.len() / 8 {
+//         s.clone_from_slice(&DICT[i*8..i*8+8]);
+//         dict.insert(u64::from_be_bytes(s));
+//     }
+//
+//     return dict;
+// }
+//
+/// Reads the file at file_path into a vector, line for line, and returns it.
+pub fn read_board(file_path: String) -> Vec<String> {
+    let file = File::open(file_path).unwrap();
+    let reader = BufReader::new(file);
+    reader.lines().map(|line| line.unwrap()).collect()
+}
+
+/// Parses the raw board using alphabet to provide the correct mapping of letters to their
+/// codes. The first BOARD_SIZE * BOARD_SIZE symbols are the letters, which are parsed into
+/// the first return value. The symbols after them are the multipliers of each tile: 2 and 3
+/// multiply the word, which are parsed into the second return value, while D and T multiply
+/// the letter, which are parsed into the third return value.
+pub fn parse_board_and_mults(
+    raw_board: Vec<String>,
+    alphabet: &Alphabet) -> ([u8; BOARD_SIZE * BOARD_SIZE], [u8; BOARD_SIZE * BOARD_SIZE], [u8; BOARD_SIZE * BOARD_SIZE]) {
+    try_parse_board_and_mults(raw_board, alphabet).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Parses the raw board as parse_board_and_mults does, returning an error rather than panicking
+/// if the board is too short or has a symbol which is not a letter of alphabet.
+pub fn try_parse_board_and_mults(
+    raw_board: Vec<String>,
+    alphabet: &Alphabet) -> Result<(Tiles, Tiles, Tiles), String> {
+    let mut board = [0; BOARD_SIZE * BOARD_SIZE];
+    let mut word_mults = [1; BOARD_SIZE * BOARD_SIZE];
+    let mut letter_mults = [1; BOARD_SIZE * BOARD_SIZE];
+    let mut all_chars = Vec::with_capacity(2 * BOARD_SIZE * BOARD_SIZE);
+
+    for line in raw_board {
+        // Letters may be typed as a base letter followed by combining accents.
+        all_chars.extend(line.nfc().filter(|c| !c.is_whitespace()));
+
+        if all_chars.len() > 2 * BOARD_SIZE * BOARD_SIZE {
+            break;
+        }
+    }
+
+    if all_chars.len() < 2 * BOARD_SIZE * BOARD_SIZE {
+        return Err(format!("The board has {} symbols, but needs {} letters followed by {} multipliers.",
+                           all_chars.len(), BOARD_SIZE * BOARD_SIZE, BOARD_SIZE * BOARD_SIZE));
+    }
+
+    for i in 0..BOARD_SIZE * BOARD_SIZE {
+        board[i] = alphabet.code(all_chars[i])
+            .ok_or(format!("{} is not a letter of the alphabet.", all_chars[i]))?;
+        match all_chars[BOARD_SIZE * BOARD_SIZE + i] {
+            '2' => word_mults[i] = 2,
+            '3' => word_mults[i] = 3,
+            'D' => letter_mults[i] = 2,
+            'T' => letter_mults[i] = 3,
+            _ => {}
+        };
+    }
+
+    Ok((board, word_mults, letter_mults))
+}
+
+// /// Takes the u64 mults (which correspond to characters in the alphabet), and maps them
+// /// to their usize multipliers.
+// fn parse_word_mults_to_int_mults(word_mults: &Vec<Vec<u64>>) -> Vec<Vec<u16>> {
+//     let mut word_int_mults = Vec::new();
+//
+//     for line in word_mults {
+//         word_int_mults.push(line.iter().map(|l| match *l {
+//             TWO_U64 => 2,
+//             THREE_U64 => 3,
+//             _ => 1,
+//         }).collect());
+//     }
+//     return word_int_mults;
+// }
+
+/// Returns the points for each letter on the board.
+pub fn get_points(board: &[u8; BOARD_SIZE * BOARD_SIZE], letter_mults: &[u8; BOARD_SIZE * BOARD_SIZE],
+                  alphabet: &Alphabet) -> [u8; BOARD_SIZE * BOARD_SIZE] {
+    let mut points = [0; BOARD_SIZE * BOARD_SIZE];
+
+    for (index, (letter, mult)) in board.iter().zip(letter_mults).enumerate() {
+        points[index] = alphabet.value(*letter) * mult;
+    }
+
+    points
+}
+
+/// Generates a graph of all possible neighbouring vertices, represented with adjacency lists.
+pub fn gen_graph() -> Vec<Vec<u8>> {
+    let mut graph: Vec<Vec<u8>> = Vec::new();
+    let directions: [(i8, i8); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1),
+        (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+    for i in 0..BOARD_SIZE_I8 {
+        for j in 0..BOARD_SIZE_I8 {
+            let mut neighbours = Vec::new();
+            for (cx, cy) in &directions {
+                let x = i + *cx;
+                let y = j + *cy;
+                if (0..4).contains(&x) && (0..4).contains(&y) {
+                    neighbours.push(((x << 2) | y) as u8);
+                }
+            }
+            graph.push(neighbours);
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::{Board, vec_to_path};
+    use crate::alphabet::Alphabet;
+    use crate::dictionary::{Dictionary, Store};
+    use crate::store::HashStore;
+
+    #[test]
+    fn writes_words_in_the_format_of_words_txt() {
+        let alphabet = Alphabet::english();
+        let store = Store::Hashed(HashStore::from_words(Default::default(), alphabet.bits_per_letter()));
+        let dictionary = Arc::new(Dictionary::new("test", alphabet, store));
+        let mut board = Board::new([1; 16], [1; 16], [1; 16], dictionary);
+        // The shorter word comes after the longest, as the line buffer is reused between words.
+        let longest = [(0, 0), (0, 1), (0, 2), (0, 3), (1, 3), (1, 2), (1, 1), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3)];
+        board.word_info = vec![
+            ("AAAAAAAAAAAA".to_string(), 62, vec_to_path(&longest)),
+            ("AA".to_string(), 2, vec_to_path(&[(3, 3), (2, 2)])),
+        ];
+
+        let mut out = Vec::new();
+        board.write_words(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+AAAAAAAAAAAA, 62, [(0, 0), (0, 1), (0, 2), (0, 3), (1, 3), (1, 2), (1, 1), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3)]
+AA, 2, [(3, 3), (2, 2)]
+");
+    }
+}
@@ -1,469 +1,600 @@
-use std::collections::{HashSet, HashMap};
-use std::fs::File;
-use std::io::{BufReader, BufRead, Read, Write, BufWriter};
-use std::time::Instant;
-use fnv::{FnvHashSet, FnvHasher};
-use std::hash::BuildHasherDefault;
-
-// const PREFIXES: &'static [u8] = include_bytes!("../data/prefixes/binary.bin");
-// const DICT: &'static [u8] = include_bytes!("../data/TWL06/binary.bin");
-
-/// Source: https://stackoverflow.com/questions/27582739/how-do-i-create-a-hashmap-literal
-macro_rules! map (
-    { $($key:expr => $value:expr),+ } => {
-        {
-            let mut m = ::std::collections::HashMap::new();
-            $(
-                m.insert($key, $value);
-            )+
-            m
-        }
-     };
-);
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::path::Path;
+use ruzzle_solver::{Board, PATH_TO_BOARD, read_board, parse_board_and_mults};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::compare::compare;
+use ruzzle_solver::dictionary::{Dictionary, DictionaryConfig, Layer, LayerKind, Registry, Source, DEFAULT_DICTIONARY,
+                                PATH_TO_CONFIG};
+use ruzzle_solver::drill::{PlayerStats, check_player_name, run_drill};
+use ruzzle_solver::filter::Filter;
+use ruzzle_solver::game::{Match, ROUNDS, play_turn};
+use ruzzle_solver::order::{Grouping, SortOrder};
+use ruzzle_solver::play::{Session, run_repl};
+use ruzzle_solver::plan::{TimeModel, plan_round};
+use ruzzle_solver::pretty::{PrettyStyle, render_path, write_pretty};
+use ruzzle_solver::random::Rng;
+use ruzzle_solver::strategy::{STRATEGY_NAMES, strategy_by_name};
+use ruzzle_solver::svg::render_svg;
+use ruzzle_solver::trainer::{HistoryEntry, TimedLines, append_history, play_round, read_history, write_progress};
+use ruzzle_solver::gesture::{BoardGeometry, GestureFormat, write_gestures};
+use ruzzle_solver::screenshot::{Palette, Region, Templates, read_board_image, read_png};
+use ruzzle_solver::simulate::{Bot, simulate};
+
+/// How the board is played interactively.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PlayMode {
+    /// Boards are typed at a prompt, which then answers commands about their words.
+    Repl,
+    /// The words of the board are browsed full screen.
+    Tui,
+}
 
-// const PATH_TO_PREFIXES: &str = r"data/prefixes/";
-// const PATH_TO_DICTIONARY: &str = r"data/";
-const PATH_TO_BOARD: &str = r"board.txt";
+/// The bots played against each other by --simulate, unless --bots is given.
+const DEFAULT_BOTS: &str = "novice:0.2:0.6:-0.5,casual:0.5:1:0,expert:0.9:1.5:0.5";
 
-const MIN_WORD_LEN: u8 = 2;
-const MAX_WORD_LEN: u8 = 12;
+/// Determines which words are kept when solving the board.
+enum Mode {
+    All,
+    TopK(usize),
+    MinScore(u16),
+}
 
-const BOARD_SIZE: usize = 4;
-const BOARD_SIZE_I8: i8 = BOARD_SIZE as i8;
-const POINT_VALS: [u8; 27] = [0, 1, 4, 4, 2, 1, 4, 3, 4, 1, 10, 5, 1, 3, 1, 1, 4,
-    10, 1, 1, 1, 2, 4, 4, 8, 4, 8];
+struct Options {
+    mode: Mode,
+    time_budget: Option<Duration>,
+    plan: Option<f32>,
+    time_model: TimeModel,
+    gestures: Option<GestureFormat>,
+    geometry: BoardGeometry,
+    image: Option<String>,
+    region: Option<Region>,
+    alphabet: Alphabet,
+    word_list: Option<String>,
+    dictionary: String,
+    config: Option<String>,
+    layers: Vec<Layer>,
+    write_mapped: Option<(String, String)>,
+    write_fst: Option<String>,
+    metadata: Option<String>,
+    json: Option<String>,
+    filters: Vec<String>,
+    order: Option<SortOrder>,
+    grouping: Option<Grouping>,
+    pretty: Option<PrettyStyle>,
+    svg: Option<String>,
+    svg_words: usize,
+    play: Option<PlayMode>,
+    train: Option<Duration>,
+    history: String,
+    player: String,
+    stats: String,
+    drill: Option<usize>,
+    simulate: Option<usize>,
+    bots: Vec<Bot>,
+    seed: u64,
+    round_time: f32,
+    players: Option<String>,
+    compare: Option<(String, String)>,
+    strategy: Option<String>,
+}
 
-// These options can be tweaked to improve performance if necessary.
-const PREFIX_LOWER_BOUND: u8 = 2;
-const PREFIX_UPPER_BOUND: u8 = 8;
+/// Parses the command line arguments: --top-k N keeps the N best words,
+/// --min-score S keeps the words scoring at least S, and --time-budget MS
+/// stops searching for words after MS milliseconds. Otherwise, --strategy stack|recursive|string
+/// picks the search which finds every word (stack by default). --plan SECONDS prints the words to enter
+/// in a round lasting SECONDS, using the time model given by --time-model WORD,CELL,MOVE.
+/// --gestures json|adb|xdotool writes the swipes for the planned words (or every word, if
+/// there is no plan) to gestures.json or gestures.sh, for a board placed on the screen as given
+/// by --geometry X,Y,PITCH. --image PATH reads the board from a PNG screenshot rather than
+/// board.txt, from the part of the screenshot given by --region X,Y,WIDTH,HEIGHT.
+/// --alphabet NAME uses one of the built in alphabets (en, fr, de, it, es or sv), or reads one
+/// from the file NAME, and --word-list PATH reads the dictionary from a list of words using that
+/// alphabet. Otherwise, --dict NAME picks the dictionary named NAME from the configuration file
+/// given by --dictionaries PATH (data/dictionaries.txt by default). --allow PATH and --deny PATH
+/// add or remove the words listed in PATH from the dictionary, and may be given several times.
+/// --write-mapped WORDS,PREFIXES writes the dictionary to sorted tables which can be memory
+/// mapped, and --write-fst PATH writes it to a transducer, rather than solving the board.
+/// --metadata PATH reads definitions of the dictionary's words from PATH, and --json PATH writes
+/// the distinct words, along with their definitions, to PATH. --filter EXPRESSION keeps only
+/// the words matching EXPRESSION (see Filter::from_str), and may be given several times.
+/// --sort KEYS orders the words by a comma separated list of keys (score, length, alpha, start
+/// or path), and --group length|start splits words.txt into groups by length or starting cell.
+/// --pretty plain|colour draws the swipe of each planned word (or every word, if there is no
+/// plan) on the board. --svg PATH draws the first N of those words, as given by --svg-words N
+/// (1 by default), to an SVG image, or to a PNG image if PATH ends in .png. --play repl reads
+/// boards typed at a prompt rather than board.txt, and --play tui browses the words full screen.
+/// --train SECONDS plays a practice round on the board, adding it to the history file given by
+/// --history PATH (history.tsv by default), and the words found and missed by the player named
+/// by --player NAME to the statistics file given by --stats PATH (stats.tsv by default).
+/// --drill N drills the player on N of the words they missed which are due, rather than solving
+/// a board. --simulate N plays the bots given by --bots NAME:COVERAGE:SPEED:BIAS,... against
+/// each other on N random boards seeded from --seed SEED, in rounds of --round-time SECONDS
+/// (120 by default), using the time model, and prints their scores and win rates. --match
+/// PLAYERS plays a three round match on random boards seeded from --seed SEED, between a comma
+/// separated list of players taking turns at the keyboard, and bots given as for --bots.
+/// --compare A,B compares the words listed in the files A and B, one per line, which two
+/// players entered on the board, with each other and with every word in the board. Two player
+/// matches are compared in the same way after each round.
+fn parse_args() -> Options {
+    let mut options = Options {
+        mode: Mode::All,
+        time_budget: None,
+        plan: None,
+        time_model: TimeModel::default(),
+        gestures: None,
+        geometry: BoardGeometry { origin: (135, 800), pitch: 270 },
+        image: None,
+        region: None,
+        alphabet: Alphabet::english(),
+        word_list: None,
+        dictionary: DEFAULT_DICTIONARY.to_string(),
+        config: None,
+        layers: Vec::new(),
+        write_mapped: None,
+        write_fst: None,
+        metadata: None,
+        json: None,
+        filters: Vec::new(),
+        order: None,
+        grouping: None,
+        pretty: None,
+        svg: None,
+        svg_words: 1,
+        play: None,
+        train: None,
+        history: "./history.tsv".to_string(),
+        player: "player".to_string(),
+        stats: "./stats.tsv".to_string(),
+        drill: None,
+        simulate: None,
+        bots: parse_bots(DEFAULT_BOTS),
+        seed: 1,
+        round_time: 120.,
+        players: None,
+        compare: None,
+        strategy: None,
+    };
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        match (arg.as_str(), value) {
+            ("--top-k", Some(k)) => options.mode = Mode::TopK(k.parse().expect("--top-k expects a number.")),
+            ("--min-score", Some(s)) => options.mode = Mode::MinScore(s.parse().expect("--min-score expects a number.")),
+            ("--time-budget", Some(ms)) => options.time_budget = Some(Duration::from_millis(
+                ms.parse().expect("--time-budget expects a number of milliseconds."))),
+            ("--plan", Some(secs)) => options.plan = Some(secs.parse().expect("--plan expects a number of seconds.")),
+            ("--time-model", Some(model)) => options.time_model = model.parse().unwrap_or_else(|err| panic!("{}", err)),
+            ("--gestures", Some(format)) => options.gestures = Some(GestureFormat::from_name(&format)
+                .expect("--gestures expects one of json, adb or xdotool.")),
+            ("--geometry", Some(geometry)) => options.geometry = parse_geometry(&geometry),
+            ("--image", Some(path)) => options.image = Some(path),
+            ("--region", Some(region)) => options.region = Some(parse_region(&region)),
+            ("--alphabet", Some(name)) => options.alphabet = Alphabet::by_name(&name)
+                .map_or_else(|| Alphabet::read(&name, &name), Ok)
+                .unwrap_or_else(|err| panic!("{}", err)),
+            ("--word-list", Some(path)) => options.word_list = Some(path),
+            ("--dict", Some(name)) => options.dictionary = name,
+            ("--dictionaries", Some(path)) => options.config = Some(path),
+            ("--allow", Some(path)) => options.layers.push(Layer { kind: LayerKind::Allow, path }),
+            ("--deny", Some(path)) => options.layers.push(Layer { kind: LayerKind::Deny, path }),
+            ("--write-mapped", Some(paths)) => options.write_mapped = Some(match paths.split_once(',') {
+                Some((words, prefixes)) => (words.to_string(), prefixes.to_string()),
+                None => panic!("--write-mapped expects two paths: WORDS,PREFIXES."),
+            }),
+            ("--write-fst", Some(path)) => options.write_fst = Some(path),
+            ("--metadata", Some(path)) => options.metadata = Some(path),
+            ("--json", Some(path)) => options.json = Some(path),
+            ("--filter", Some(expression)) => options.filters.push(expression),
+            ("--sort", Some(keys)) => options.order = Some(keys.parse().unwrap_or_else(|err| panic!("{}", err))),
+            ("--group", Some(grouping)) => options.grouping = Some(Grouping::from_name(&grouping)
+                .expect("--group expects length or start.")),
+            ("--pretty", Some(style)) => options.pretty = Some(PrettyStyle::from_name(&style)
+                .expect("--pretty expects plain or colour.")),
+            ("--play", Some(mode)) => options.play = Some(match mode.as_str() {
+                "repl" => PlayMode::Repl,
+                "tui" => PlayMode::Tui,
+                _ => panic!("--play expects repl or tui."),
+            }),
+            ("--train", Some(secs)) => options.train = Some(Duration::from_secs_f32(
+                secs.parse().expect("--train expects a number of seconds."))),
+            ("--history", Some(path)) => options.history = path,
+            ("--player", Some(name)) => {
+                check_player_name(&name).unwrap_or_else(|err| panic!("{}", err));
+                options.player = name;
+            }
+            ("--stats", Some(path)) => options.stats = path,
+            ("--drill", Some(n)) => options.drill = Some(n.parse().expect("--drill expects a number of words.")),
+            ("--simulate", Some(n)) => options.simulate = Some(n.parse().expect("--simulate expects a number of rounds.")),
+            ("--bots", Some(bots)) => options.bots = parse_bots(&bots),
+            ("--seed", Some(seed)) => options.seed = seed.parse().expect("--seed expects a number."),
+            ("--round-time", Some(secs)) => options.round_time = secs.parse().expect("--round-time expects a number of seconds."),
+            ("--match", Some(players)) => options.players = Some(players),
+            ("--compare", Some(paths)) => options.compare = Some(match paths.split_once(',') {
+                Some((a, b)) => (a.to_string(), b.to_string()),
+                None => panic!("--compare expects two paths: A,B."),
+            }),
+            ("--strategy", Some(name)) => {
+                assert!(STRATEGY_NAMES.contains(&name.as_str()), "--strategy expects one of {}.", STRATEGY_NAMES.join(", "));
+                options.strategy = Some(name);
+            }
+            ("--svg", Some(path)) => options.svg = Some(path),
+            ("--svg-words", Some(n)) => options.svg_words = n.parse().expect("--svg-words expects a number."),
+            _ => panic!("Unrecognized argument {}.", arg),
+        };
+    }
 
-const U64_TO_CHAR: [char; 30] = ['!', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I',
-    'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '2', '3', '-'];
+    if options.word_list.is_none() && options.alphabet.name() != "en" {
+        panic!("--alphabet requires a dictionary to be given by --word-list.");
+    }
 
-// const U64_TO_U8: [u8; 30] = *b"!ABCDEFGHIJKLMNOPQRSTUVWXYZ23-";
+    if options.time_budget.is_some() && !matches!(options.mode, Mode::All) {
+        panic!("--time-budget can not be combined with --top-k or --min-score.");
+    }
 
-// Convenience.
-const D_U8: u8 = 4;
-const T_U8: u8 = 20;
+    if options.strategy.is_some() && (options.time_budget.is_some() || !matches!(options.mode, Mode::All)) {
+        panic!("--strategy can not be combined with --time-budget, --top-k or --min-score.");
+    }
 
+    options
+}
 
-const TWO_U8: u8 = 27 as u8;
-const THREE_U8: u8 = 28 as u8;
+/// Rasterizes an SVG image to PNG, which needs the png-export feature.
+#[cfg(feature = "png-export")]
+fn rasterize(svg: &str) -> Vec<u8> {
+    ruzzle_solver::svg::svg_to_png(svg).unwrap_or_else(|err| panic!("{}", err))
+}
 
-// const TWO_U64: u64 = 27;
-// const THREE_U64: u64 = 28;
-// const DASH_U64: u64 = 29;
+#[cfg(not(feature = "png-export"))]
+fn rasterize(_svg: &str) -> Vec<u8> {
+    panic!("PNG images need the png-export feature.");
+}
 
-//const DICT_BYTES: &'static [u8] = include_bytes!("../data/TWL06/binary.bin");
-//const PREFIX_BYTES: &'static [u8] = include_bytes!("../data/prefixes/binary.bin");
+/// Browses the words of the board full screen, which needs the tui feature.
+#[cfg(feature = "tui")]
+fn browse_full_screen(board: &Board) {
+    ruzzle_solver::tui::run_tui(board).expect("The full screen view failed.");
+}
 
-/// Maintains the board state and words found in the board.
-struct Board {
-    //              word, score, path
-    word_info: Vec<(String, u16, u64)>,
-    board: [u8; BOARD_SIZE * BOARD_SIZE],
-    points: [u8; BOARD_SIZE * BOARD_SIZE],
-    word_int_mults: [u8; BOARD_SIZE * BOARD_SIZE],
-    prefixes: HashSet<u64, BuildHasherDefault<FnvHasher>>,
-    dictionary: HashSet<u64, BuildHasherDefault<FnvHasher>>,
+#[cfg(not(feature = "tui"))]
+fn browse_full_screen(_board: &Board) {
+    panic!("--play tui needs the tui feature.");
 }
 
-impl Board {
-    fn sort_entries(self: &mut Self) {
-        self.word_info.sort_by(|a, b| b.1.cmp(&a.1))
-    }
+/// Returns the current time, in seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs())
+}
 
-    fn write_to_file(self: &Self) {
-        let file = File::create("./words.txt").unwrap();
-        let mut buf_writer = BufWriter::with_capacity(24 * 1024, file);
-
-        let mut path_buf = *b"(0, 0), (0, 0), (0, 0), (0, 0), \
-        (0, 0), (0, 0), (0, 0), (0, 0), \
-        (0, 0), (0, 0), (0, 0), (0, 0), ";
-        let mut path_buf_ = [(0, 0); 12];
-        for (word, score, mut path_as_u64) in self.word_info.iter() {
-            // assert!(path.len() * 8 <= path_buf.len());
-            let max_bit = path_to_vec_buffered(path_as_u64, &mut path_buf_);
-            for (i, &(x, y)) in path_buf_[max_bit..11].iter().enumerate() {
-                path_buf[i * 8 + 1] = x + 48;
-                path_buf[i * 8 + 4] = y + 48;
-            }
-            let (x, y) = path_buf_[11];
-            let i = 11 - max_bit;
-            path_buf[i * 8 + 1] = x + 48;
-            path_buf[i * 8 + 4] = y + 48;
-            path_buf[i * 8 + 6] = b']';
-            path_buf[i * 8 + 7] = b'\n';
-
-            buf_writer.write_all(format!("{}, {}, [", word, *score, ).as_bytes()).unwrap();
-            buf_writer.write_all(&path_buf[..i * 8 + 8]).unwrap();
-            path_buf[i * 8 + 6] = b',';
-            path_buf[i * 8 + 7] = b' ';
-        }
-    }
+/// Plays a practice round on board, then reports how it went, adds it to the history file, and
+/// records the words found and missed in the statistics file.
+fn train(board: &Board, round_time: Duration, options: &Options, style: PrettyStyle) {
+    print!("{}", render_path(board, 0, style));
+    let stdout = io::stdout();
+    let mut input = TimedLines::new(BufReader::new(io::stdin()));
+    let round = play_round(board, round_time, &mut input, &mut stdout.lock())
+        .expect("Reading the words failed.");
+    let report = round.report(10);
+    report.write(&mut stdout.lock()).expect("Writing the report failed.");
+
+    append_history(&options.history, &HistoryEntry::new(board, &report)).unwrap_or_else(|err| panic!("{}", err));
+    let history = read_history(&options.history).unwrap_or_else(|err| panic!("{}", err));
+    write_progress(&history, 5, &mut stdout.lock()).expect("Writing the progress failed.");
+
+    let mut stats = PlayerStats::read(&options.stats).unwrap_or_else(|err| panic!("{}", err));
+    stats.record_round(&options.player, &round, now());
+    stats.write(&options.stats).unwrap_or_else(|err| panic!("{}", err));
 }
 
-/// A non recursive depth first search which identifies all words, and adds them to
-/// board.word_info_as_str with their string representation, score and path. Returns nothing.
-fn dfs(board: &mut Board, graph: Vec<Vec<u8>>) {
-    let mut stack: Vec<(u64, u64, u16, u8, u8, u16)> = Vec::with_capacity(120);
-    for i in 0..BOARD_SIZE * BOARD_SIZE {
-        stack.push(((0b10000 | i) as u64,
-        board.board[i] as u64,
-        board.points[i] as u16,
-        board.word_int_mults[i],
-        1, 0));
+/// Drills the player on at most size of their missed words which are due, and records the
+/// answers in the statistics file at stats_path.
+fn drill(player: &str, stats_path: &str, size: usize) {
+    let mut stats = PlayerStats::read(stats_path).unwrap_or_else(|err| panic!("{}", err));
+    let now = now();
+    let patterns = stats.missed_patterns(player, 2);
+    if !patterns.is_empty() {
+        let listed: Vec<String> = patterns.iter().take(5).map(|(pattern, missed, _)| format!("{} ({})", pattern, missed)).collect();
+        println!("The patterns you miss most: {}", listed.join(", "));
     }
-    /// Paths consist of 12 five bit vertices:
-    /// [continuation_flag:1][x:2][y:2]
-
-    // This whole thing takes about 100ns per iteration, on average (0.0006s for 6000 fn calls).
-    // Out of 72846 values, we narrow it down to 6000 -> produce 410 results.
-    // Most pruning occurs around 4-8 values. 2-3 doesn't really do much, but the cost of hashing
-    // is roughly equal to the cost of going through a full operation. Past 9 values, most of the tree
-    // is already completed.
-    while let Some((path, word, word_pts, word_mult, mut word_len, mut visited)) = stack.pop() {
-        if (word_len >= MIN_WORD_LEN) & (word_len <= MAX_WORD_LEN) & board.dictionary.contains(&word) {
-            let mut score = word_pts * (word_mult as u16);
-            if word_len > 4 {
-                score += 5 * (word_len as u16 - 4);
-            }
-
-            // Parsing words takes very little time - only ~3% of calls get this far.
-            board.word_info.push((parse_to_str(word), score, path));
-        }
 
-        let vert = path & 0xF;
-        visited |= 1 << vert;
-        word_len += 1;
-
-        for &vertex in &graph[vert as usize] {
-            if ((visited >> vertex) & 1) == 0 {
-                let temp_word = (word << 5) | (board.board[vertex as usize] as u64);
-
-                // Testing bloom filters doesn't really suggest a significant difference.
-                if word_len <= PREFIX_UPPER_BOUND && !board.prefixes.contains(&temp_word) {
-                    continue;
-                }
-
-                let path_clone = (path << 5) | 0b10000 | (vertex as u64);
+    let items = stats.drill(player, now, size);
+    if items.is_empty() {
+        println!("No words are due for {}.", player);
+        return;
+    }
+    let stdout = io::stdout();
+    run_drill(&mut stats, player, &items, now, io::stdin().lock(), &mut stdout.lock()).expect("The drill failed.");
+    stats.write(stats_path).unwrap_or_else(|err| panic!("{}", err));
+}
 
-                if word_len == MAX_WORD_LEN {
-                    if board.dictionary.contains(&temp_word) {
-                        let score = word_pts * (word_mult as u16) + 40;
-                        board.word_info.push((parse_to_str(temp_word),
-                                              score, path_clone));
+/// Plays a match between players, each either a name for someone at the keyboard or a bot
+/// (see Bot::from_str), then prints the result.
+fn play_match(dictionary: &Arc<Dictionary>, players: &str, options: &Options, style: PrettyStyle) {
+    let bots: Vec<Option<Bot>> = players.split(',')
+        .map(|player| player.contains(':').then(|| player.parse().unwrap_or_else(|err| panic!("{}", err))))
+        .collect();
+    let names: Vec<&str> = players.split(',').zip(&bots)
+        .map(|(player, bot)| bot.as_ref().map_or(player.trim(), |bot| bot.name.as_str()))
+        .collect();
+    let mut game = Match::generate(&names, dictionary, options.seed).unwrap_or_else(|err| panic!("{}", err));
+    let mut rng = Rng::new(options.seed);
+    let stdout = io::stdout();
+    let mut stdin = io::stdin().lock();
+
+    for round in 0..ROUNDS {
+        println!("Round {}", round + 1);
+        for (player, bot) in bots.iter().enumerate() {
+            let score = match bot {
+                Some(bot) => {
+                    let words = bot.play(&game.board(round).word_info, &options.time_model, options.round_time, &mut rng);
+                    for (word, _) in &words {
+                        game.guess(player, word);
                     }
-                    continue;
+                    game.round_score(round, player)
                 }
-
-                stack.push((path_clone, temp_word, word_pts + board.points[vertex as usize] as u16,
-                            word_mult * board.word_int_mults[vertex as usize], word_len, visited | (1 << vertex)));
-            }
+                None => {
+                    print!("{}", render_path(game.board(round), 0, style));
+                    play_turn(&mut game, player, Duration::from_secs_f32(options.round_time), &mut stdin, &mut stdout.lock())
+                        .expect("Reading the words failed.")
+                }
+            };
+            println!("{} scored {} points with {} words.", names[player], score, game.found(round, player).len());
+        }
+        if names.len() == 2 {
+            game.compare(round, 0, 1).write(&mut stdout.lock()).expect("Writing the comparison failed.");
         }
+        game.end_round();
     }
+    game.result().write(&mut stdout.lock()).expect("Writing the result failed.");
 }
 
+/// Parses a comma separated list of bots (see Bot::from_str).
+fn parse_bots(bots: &str) -> Vec<Bot> {
+    bots.split(',').map(|bot| bot.parse().unwrap_or_else(|err| panic!("{}", err))).collect()
+}
 
-// /// Returns a u64 representation of any string that is twelve characters or less, and only
-// /// contains the letters A-Z. Every five bits, up until 60 bits or five zero bits occur,
-// /// correspond to the letter in U64_TO_CHAR with the same index (eg. as usize).
-// fn string_to_u64(string_to_convert: &String, str_to_u64: &HashMap<char, u8>) -> u64 {
-//     let mut output: u64 = 0;
-//     for c_u64 in string_to_convert.chars().map(|c| str_to_u64[&c]) {
-//         output = (output << 5) | (c_u64 as u64);
-//     }
-//     return output;
-// }
-
-/// Generates the string representation of any string that is represented in the first 60
-/// bits of str_as_num, where each group of five consecutive bits corresponds the the character
-/// at the index in U64_TO_CHAR.
-fn parse_to_str(str_as_num: u64) -> String {
-    let mut str_repr = [U64_TO_CHAR[0]; 12];
-    let mut str_numbers = str_as_num;
-    let mut max_bit = 12;
-
-    for _ in 0..12 {
-        // Read the last five bits.
-        let val = (str_numbers & 0b11111) as usize;
-        if val != 0 {
-            max_bit -= 1;
-            str_numbers >>= 5;
-            str_repr[max_bit] = U64_TO_CHAR[val];
-        } else {
-            break;
-        }
-    }
-
-    // unsafe {
-    //     return std::str::from_utf8_unchecked(&str_repr[max_bit..]).to_string();
-    // }
-    str_repr[max_bit..].iter().collect()
+/// Parses a board geometry of the form X,Y,PITCH, in pixels.
+fn parse_geometry(geometry: &str) -> BoardGeometry {
+    let values: Vec<u32> = geometry.split(',')
+        .map(|value| value.trim().parse().expect("--geometry expects numbers of pixels."))
+        .collect();
+    assert_eq!(values.len(), 3, "--geometry expects three values: X,Y,PITCH.");
+    BoardGeometry { origin: (values[0], values[1]), pitch: values[2] }
 }
 
-fn path_to_vec_buffered(path_as_u64: u64, buf: &mut [(u8, u8); 12]) -> usize {
-    let mut mut_path = path_as_u64;
-    let mut max_bit = 12;
-
-    while mut_path & 0b10000 == 0b10000 {
-        let y = (mut_path & 0b11) as u8;
-        mut_path >>= 2;
-        let x = (mut_path & 0b11) as u8;
-        mut_path >>= 3;
-        max_bit -= 1;
-        buf[max_bit] = (x, y);
-    }
+/// Parses a screenshot region of the form X,Y,WIDTH,HEIGHT, in pixels.
+fn parse_region(region: &str) -> Region {
+    let values: Vec<u32> = region.split(',')
+        .map(|value| value.trim().parse().expect("--region expects numbers of pixels."))
+        .collect();
+    assert_eq!(values.len(), 4, "--region expects four values: X,Y,WIDTH,HEIGHT.");
+    Region { x: values[0], y: values[1], width: values[2], height: values[3] }
+}
 
-    max_bit
+/// Reads the board from the screenshot at path, recognising the letters of alphabet. Without a
+/// region, the whole screenshot is taken to be the board.
+fn read_screenshot(path: &str, region: Option<Region>, alphabet: &Alphabet) -> Vec<String> {
+    let image = read_png(path).expect("Reading the screenshot failed.");
+    let region = region.unwrap_or(Region { x: 0, y: 0, width: image.width, height: image.height });
+    let templates = Templates::for_alphabet(alphabet).unwrap_or_else(|err| panic!("{}", err));
+    read_board_image(&image, &region, &Palette::default(), &templates)
+        .unwrap_or_else(|err| panic!("{}", err))
 }
 
-fn path_to_vec(path_as_u64: u64) -> Vec<(u8, u8)> {
-    let mut path_repr = [(0, 0); 12];
+/// Reads the dictionary registry from the configuration file, falling back to the dictionaries
+/// shipped in data/ if there is no configuration file.
+fn read_registry(config: Option<&str>) -> Registry {
+    match config {
+        Some(path) => Registry::read_config(path),
+        None if Path::new(PATH_TO_CONFIG).exists() => Registry::read_config(PATH_TO_CONFIG),
+        None => Ok(Registry::with_defaults()),
+    }.unwrap_or_else(|err| panic!("{}", err))
+}
 
-    let mut mut_path = path_as_u64;
-    let mut max_bit = 12;
+fn main() {
+    let mut options = parse_args();
+    let mut registry = read_registry(options.config.as_deref());
+    if let Some(path) = options.word_list.take() {
+        registry.register(DictionaryConfig {
+            name: path.clone(),
+            alphabet: options.alphabet.clone(),
+            source: Source::WordList(path.clone()),
+            metadata: None,
+        });
+        options.dictionary = path;
+    }
+    if !options.layers.is_empty() {
+        let name = format!("{} (with allow and deny lists)", options.dictionary);
+        let alphabet = registry.config(&options.dictionary).unwrap_or_else(|err| panic!("{}", err)).alphabet.clone();
+        registry.register(DictionaryConfig {
+            name: name.clone(),
+            alphabet,
+            source: Source::Layered { base: options.dictionary, layers: options.layers.clone() },
+            metadata: None,
+        });
+        options.dictionary = name;
+    }
+    if let Some(path) = options.metadata.take() {
+        let mut config = registry.config(&options.dictionary).unwrap_or_else(|err| panic!("{}", err)).clone();
+        config.metadata = Some(path);
+        registry.register(config);
+    }
 
-    while mut_path & 0b10000 == 0b10000 {
-        let y = (mut_path & 0b11) as u8;
-        mut_path >>= 2;
-        let x = (mut_path & 0b11) as u8;
-        mut_path >>= 3;
-        max_bit -= 1;
-        path_repr[max_bit] = (x, y);
+    if let Some((words_path, prefixes_path)) = &options.write_mapped {
+        let dictionary = registry.get(&options.dictionary).unwrap_or_else(|err| panic!("{}", err));
+        dictionary.write_mapped(words_path, prefixes_path).unwrap_or_else(|err| panic!("{}", err));
+        println!("Wrote the {} dictionary to {} and {}.", dictionary.name, words_path, prefixes_path);
+        return;
     }
 
-    return path_repr[max_bit..].to_vec();
-}
+    if let Some(path) = &options.write_fst {
+        let dictionary = registry.get(&options.dictionary).unwrap_or_else(|err| panic!("{}", err));
+        dictionary.write_fst(path).unwrap_or_else(|err| panic!("{}", err));
+        println!("Wrote the {} dictionary to {}.", dictionary.name, path);
+        return;
+    }
 
+    let now = Instant::now();
 
-// /// Returns a HashSet containing all prefixes of length PREFIX_LOWER_BOUND to PREFIX_UPPER_BOUND
-// /// in their u64 representation.
-// fn read_prefixes(str_to_u64: &HashMap<char, u8>) -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
-//     let mut prefixes = FnvHashSet::with_capacity_and_hasher(250000, Default::default());
-//     let file = File::open("./data/prefixes/prefixes.txt").unwrap();
-//
-//     let mut reader = BufReader::new(file);
-//     let mut s = String::new();
-//
-//     while let res = reader.read_line(&mut s).expect("Reading prefixes failed.") {
-//         if res == 0 {
-//             break;
-//         }
-//         prefixes.insert(string_to_u64(&s[..res - 2].to_owned(), str_to_u64));
-//     }
-//     return prefixes;
-// }
-
-/// Reads files which have been preprocessed to be compressed string representations.
-fn read_binary_prefixes() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
-    let mut prefixes = FnvHashSet::with_capacity_and_hasher(275944 + 1, Default::default());
-    let file = File::open("./data/prefixes/binary.bin").unwrap();
-
-    let mut reader = BufReader::new(file);
-    let mut s = [0; 8];
-
-    while reader.read(&mut s).expect("Reading binary prefix file failed.") == 8 {
-        prefixes.insert(u64::from_be_bytes(s));
+    if let Some(size) = options.drill {
+        drill(&options.player, &options.stats, size);
+        return;
     }
 
-    return prefixes;
-}
+    if let Some(rounds) = options.simulate {
+        let dictionary = registry.get(&options.dictionary).unwrap_or_else(|err| panic!("{}", err));
+        let simulation = simulate(&dictionary, &options.bots, rounds, options.seed, options.round_time,
+                                  &options.time_model);
+        simulation.write(&mut io::stdout().lock()).expect("Writing the simulation failed.");
+        return;
+    }
 
+    if let Some(players) = &options.players {
+        let dictionary = registry.get(&options.dictionary).unwrap_or_else(|err| panic!("{}", err));
+        play_match(&dictionary, players, &options, options.pretty.unwrap_or(PrettyStyle::Plain));
+        return;
+    }
 
-// fn parse_prefixes() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
-//     assert_eq!(PREFIXES.len() & 0b111, 0);
-//     let mut prefixes = FnvHashSet::with_capacity_and_hasher(275944 + 1, Default::default());
-//
-//     let mut s = [0u8; 8];
-//
-//     for i in 0..PREFIXES.len() / 8 {
-//         s.clone_from_slice(&PREFIXES[i*8..i*8+8]);
-//         prefixes.insert(u64::from_be_bytes(s));
-//     }
-//
-//     return prefixes;
-// }
-//
-
-// /// Returns a HashSet containing all words in their u64 representation.
-// fn read_dict(str_to_u64: &HashMap<char, u8>) -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
-//     let mut dict = FnvHashSet::with_capacity_and_hasher(200000, Default::default());
-//     let file = File::open("./data/TWL06Trimmed.txt").unwrap();
-//
-//     let mut reader = BufReader::new(file);
-//     let mut s = String::new();
-//
-//     while let res = reader.read_line(&mut s).expect("Reading dictionary failed.") {
-//         if res == 0 {
-//             break;
-//         }
-//         dict.insert(string_to_u64(&s[..res - 2].to_owned(), str_to_u64));
-//     }
-//
-//     return dict;
-// }
-
-fn read_binary_dict() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
-    let mut dict = FnvHashSet::with_capacity_and_hasher(162725 + 1, Default::default());
-    let file = File::open("./data/TWL06/binary.bin").unwrap();
-    let mut reader = BufReader::new(file);
-
-    let mut s = [0; 8];
-    while reader.read(&mut s).expect("Reading binary dictionary failed.") == 8 {
-        dict.insert(u64::from_be_bytes(s));
+    if options.play == Some(PlayMode::Repl) {
+        let dictionary = registry.get(&options.dictionary).unwrap_or_else(|err| panic!("{}", err));
+        let mut session = Session::new(dictionary, options.pretty.unwrap_or(PrettyStyle::Plain));
+        let stdout = io::stdout();
+        run_repl(&mut session, io::stdin().lock(), &mut stdout.lock()).expect("The prompt failed.");
+        return;
     }
 
-    return dict;
-}
+    let dictionary = registry.get(&options.dictionary).unwrap_or_else(|err| panic!("{}", err));
+    let raw_board = match &options.image {
+        Some(path) => read_screenshot(path, options.region, &dictionary.alphabet),
+        None => read_board(PATH_TO_BOARD.to_string()),
+    };
 
-// fn parse_dict() -> HashSet<u64, BuildHasherDefault<FnvHasher>> {
-//     assert_eq!(DICT.len() & 0b111, 0);
-//     let mut dict = FnvHashSet::with_capacity_and_hasher(162725 + 1, Default::default());
-//     let mut s = [0u8; 8];
-//
-//     for i in 0..DICT.len() / 8 {
-//         s.clone_from_slice(&DICT[i*8..i*8+8]);
-//         dict.insert(u64::from_be_bytes(s));
-//     }
-//
-//     return dict;
-// }
-//
-/// Reads the file at file_path into a vector, line for line, and returns it.
-fn read_board(file_path: String) -> Vec<String> {
-    let file = File::open(file_path).unwrap();
-    let reader = BufReader::new(file);
-    reader.lines().map(|line| line.unwrap()).collect()
-}
+    println!("Files took {}s to read.", now.elapsed().as_secs_f32());
 
-/// Parses the raw board using str_to_u64 to provide the correct mapping of
-/// characters to u64 values with the lower 5 bits set. The lines up to the first blank line
-/// are parsed into the first return value. All proceeding lines are parsed into the second
-/// return value.
-fn parse_board_and_mults(
-    raw_board: Vec<String>,
-    str_to_u8: &HashMap<char, u8>) -> ([u8; BOARD_SIZE * BOARD_SIZE], [u8; BOARD_SIZE * BOARD_SIZE]) {
-    let mut board = [0; BOARD_SIZE * BOARD_SIZE];
-    let mut word_mults = [0; BOARD_SIZE * BOARD_SIZE];
-    let mut all_chars = Vec::with_capacity(2 * BOARD_SIZE * BOARD_SIZE);
-
-    for line in raw_board {
-        let mut symbols = line
-            .chars()
-            .filter(|c| *c != ' ')
-            .map(|c| str_to_u8[&c]).collect();
-
-        all_chars.append(& mut symbols);
-
-        if all_chars.len() > 2 * BOARD_SIZE * BOARD_SIZE {
-            break;
-        }
-    }
+    let (board, word_int_mults, letter_mults) = parse_board_and_mults(raw_board, &dictionary.alphabet);
+    let mut ruzzle_board = Board::new(board, word_int_mults, letter_mults, dictionary);
 
-    assert!(all_chars.len() >= 2 * BOARD_SIZE * BOARD_SIZE);
+    let now = Instant::now();
 
-    for i in 0..BOARD_SIZE * BOARD_SIZE {
-        board[i] = all_chars[i];
-        word_mults[i] = match all_chars[BOARD_SIZE * BOARD_SIZE + i] {
-            TWO_U8 => 2,
-            THREE_U8 => 3,
-            _ => 1,
-        };
+    match options.mode {
+        Mode::All => match options.time_budget {
+            None => match &options.strategy {
+                None => ruzzle_board.solve(),
+                Some(name) => strategy_by_name(name, &ruzzle_board.dictionary)
+                    .expect("The strategy is checked when parsing the arguments.")
+                    .solve(&mut ruzzle_board),
+            },
+            Some(budget) => {
+                let mut words = Vec::with_capacity(500);
+                let status = ruzzle_board.solve_streaming(Some(budget), None, &mut words);
+                ruzzle_board.word_info = words;
+                if !status.is_complete() {
+                    println!("The search was cut short ({:?}).", status);
+                }
+            }
+        },
+        Mode::TopK(k) => ruzzle_board.solve_top_k(k),
+        Mode::MinScore(min_score) => ruzzle_board.solve_min_score(min_score),
     }
 
-    return (board, word_mults);
-}
+    println!("Board solving took {}s.", now.elapsed().as_secs_f32());
 
-// /// Takes the u64 mults (which correspond to characters in the alphabet), and maps them
-// /// to their usize multipliers.
-// fn parse_word_mults_to_int_mults(word_mults: &Vec<Vec<u64>>) -> Vec<Vec<u16>> {
-//     let mut word_int_mults = Vec::new();
-//
-//     for line in word_mults {
-//         word_int_mults.push(line.iter().map(|l| match *l {
-//             TWO_U64 => 2,
-//             THREE_U64 => 3,
-//             _ => 1,
-//         }).collect());
-//     }
-//     return word_int_mults;
-// }
-
-/// Returns the points for each letter on the board.
-fn get_points(board: &[u8; BOARD_SIZE * BOARD_SIZE], word_mults: &[u8; BOARD_SIZE * BOARD_SIZE]) -> [u8; BOARD_SIZE * BOARD_SIZE] {
-    let mut points = [0; BOARD_SIZE * BOARD_SIZE];
-
-    for (index, (letter, mult)) in board.iter().zip(word_mults).enumerate() {
-        points[index] = POINT_VALS[*letter as usize] * match *mult {
-                D_U8 => 2,
-                T_U8 => 3,
-                _ => 1,
-            };
+    if let Some(round_time) = options.train {
+        train(&ruzzle_board, round_time, &options, options.pretty.unwrap_or(PrettyStyle::Plain));
+        return;
     }
 
-    return points;
-}
-
-/// Generates a graph of all possible neighbouring vertices, represented with adjacency lists.
-fn gen_graph() -> Vec<Vec<u8>> {
-    let mut graph: Vec<Vec<u8>> = Vec::new();
-    let directions: [(i8, i8); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1),
-        (1, 1), (1, -1), (-1, 1), (-1, -1)];
-
-    for i in 0..BOARD_SIZE_I8 {
-        for j in 0..BOARD_SIZE_I8 {
-            let mut neighbours = Vec::new();
-            for (cx, cy) in &directions {
-                let x = i + *cx;
-                let y = j + *cy;
-                if (0 <= x) && (x < 4) && (0 <= y) && (y < 4) {
-                    neighbours.push(((x << 2) | y) as u8);
-                }
-            }
-            graph.push(neighbours);
-        }
+    if let Some((path_a, path_b)) = &options.compare {
+        let read_words = |path: &str| -> Vec<String> {
+            fs::read_to_string(path).unwrap_or_else(|err| panic!("The words in {} could not be read: {}", path, err))
+                .lines().map(str::to_string).collect()
+        };
+        let name = |path: &str| Path::new(path).file_stem().map_or(path.to_string(), |stem| stem.to_string_lossy().into_owned());
+        let comparison = compare(&ruzzle_board, (&name(path_a), &name(path_b)), &read_words(path_a), &read_words(path_b), 10);
+        comparison.write(&mut io::stdout().lock()).expect("Writing the comparison failed.");
+        return;
     }
-    return graph;
-}
 
+    if !options.filters.is_empty() {
+        let filter: Filter = options.filters.join(" ").parse().unwrap_or_else(|err| panic!("{}", err));
+        ruzzle_board.filter_entries(&filter);
+    }
 
-fn main() {
-    let str_to_u64_map: HashMap<char, u8> = map!{'A' => 1, 'B'=> 2, 'C'=> 3, 'D'=> 4, 'E'=> 5,
-    'F'=> 6, 'G'=> 7, 'H'=> 8, 'I'=> 9, 'J'=> 10, 'K'=> 11, 'L' => 12, 'M' => 13, 'N'=> 14,
-    'O'=> 15, 'P'=> 16, 'Q'=> 17, 'R'=> 18, 'S'=> 19, 'T'=> 20, 'U'=> 21, 'V'=> 22, 'W'=> 23,
-    'X' => 24, 'Y' => 25, 'Z'=> 26, '2' => 27, '3' => 28, '-' => 29};
+    match &options.order {
+        None => ruzzle_board.sort_entries(),
+        Some(order) => ruzzle_board.sort_entries_by(order),
+    }
+    println!("{} solutions were found using the {} dictionary.", ruzzle_board.word_info.len(),
+             ruzzle_board.dictionary.name);
+    for layer in &ruzzle_board.dictionary.layers()[1..] {
+        let count = ruzzle_board.word_info.iter()
+            .filter(|(word, _, _)| ruzzle_board.word_layer(word) == Some(layer.as_str()))
+            .count();
+        println!("{} of them came from {}.", count, layer);
+    }
 
     let now = Instant::now();
+    match options.grouping {
+        None => ruzzle_board.write_to_file(),
+        Some(grouping) => {
+            let mut writer = BufWriter::new(File::create("./words.txt").unwrap());
+            ruzzle_board.write_groups(&mut writer, grouping).expect("Writing the words failed.");
+        }
+    }
+    println!("File writing took {}s.", now.elapsed().as_secs_f32());
 
-    let raw_board = read_board(PATH_TO_BOARD.to_string());
-    let prefixes = read_binary_prefixes();
-    let dictionary = read_binary_dict();
-
-    println!("Files took {}s to read.", now.elapsed().as_secs_f32());
-
-    let (board, word_int_mults) = parse_board_and_mults(raw_board, &str_to_u64_map);
-    let points = get_points(&board, &word_int_mults);
+    ruzzle_board.dedup_entries();
+    let mut chosen = ruzzle_board.word_info.clone();
+    if let Some(order) = &options.order {
+        ruzzle_board.sort_entries_by(order);
+    }
 
-    let mut ruzzle_board = Board {
-        word_info: Vec::with_capacity(500),
-        board,
-        points,
-        word_int_mults,
-        prefixes,
-        dictionary,
-    };
+    if let Some(path) = &options.json {
+        let mut writer = BufWriter::new(File::create(path).unwrap());
+        ruzzle_board.write_json(&mut writer).expect("Writing the words as JSON failed.");
+        println!("{} distinct words were written to {}.", ruzzle_board.word_info.len(), path);
+    }
 
-    let now = Instant::now();
+    if let Some(round_time) = options.plan {
+        println!("Play list for a {}s round:", round_time);
+        let plan = plan_round(&chosen, &options.time_model, round_time);
+        for planned in &plan {
+            let definition = ruzzle_board.word_metadata(&planned.word)
+                .and_then(|info| info.definition.as_deref())
+                .unwrap_or("");
+            let line = format!("{:>6.2}s {:>5} {:<12} +{:<4} {}", planned.cumulative_time,
+                               planned.cumulative_score, planned.word, planned.score, definition);
+            println!("{}", line.trim_end());
+        }
+        chosen = plan.into_iter().map(|planned| (planned.word, planned.score, planned.path)).collect();
+    }
 
-    dfs(&mut ruzzle_board, gen_graph());
+    if let Some(style) = options.pretty {
+        let stdout = io::stdout();
+        write_pretty(&mut stdout.lock(), &ruzzle_board, &chosen, style).expect("Drawing the words failed.");
+    }
 
-    println!("Board solving took {}s.", now.elapsed().as_secs_f32());
+    if let Some(path) = &options.svg {
+        let words = &chosen[..options.svg_words.min(chosen.len())];
+        let svg = render_svg(&ruzzle_board, words);
+        let bytes = if path.ends_with(".png") { rasterize(&svg) } else { svg.into_bytes() };
+        fs::write(path, bytes).expect("Writing the image failed.");
+        println!("{} words were drawn to {}.", words.len(), path);
+    }
 
-    ruzzle_board.sort_entries();
-    println!("{} solutions were found.", ruzzle_board.word_info.len());
+    if let Some(format) = options.gestures {
+        let file_name = format!("./gestures.{}", format.extension());
+        let mut writer = BufWriter::new(File::create(&file_name).unwrap());
+        write_gestures(&mut writer, &chosen, &options.geometry, format, ruzzle_board.dictionary.metadata())
+            .expect("Writing gestures failed.");
+        println!("Gestures for {} words were written to {}.", chosen.len(), file_name);
+    }
 
-    let now = Instant::now();
-    ruzzle_board.write_to_file();
-    println!("File writing took {}s.", now.elapsed().as_secs_f32());
-}
\ No newline at end of file
+    if options.play == Some(PlayMode::Tui) {
+        browse_full_screen(&ruzzle_board);
+    }
+}
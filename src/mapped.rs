@@ -0,0 +1,69 @@
+//! Word tables stored on disk as sorted arrays, which are memory mapped and searched in place,
+//! so that loading a dictionary costs the same however many words it has.
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use memmap2::Mmap;
+
+// Identifies a sorted word table, and its version.
+const MAGIC: &[u8; 8] = b"RZWORDS1";
+
+/// A sorted array of packed words in a memory mapped file. The file starts with MAGIC,
+/// followed by the words as little endian u64s in ascending order.
+pub struct MappedWords {
+    map: Mmap,
+}
+
+impl MappedWords {
+    /// Maps the table at file_path. Nothing is read up front: pages of the file are read by the
+    /// operating system as the search touches them.
+    pub fn open(file_path: &str) -> io::Result<MappedWords> {
+        let file = File::open(file_path)?;
+        // The table must not be modified while it is mapped, which holds for the data files
+        // as they are only written by write.
+        let map = unsafe { Mmap::map(&file)? };
+
+        if map.len() < MAGIC.len() || &map[..MAGIC.len()] != MAGIC || !(map.len() - MAGIC.len()).is_multiple_of(8) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      format!("{} is not a sorted word table.", file_path)));
+        }
+
+        Ok(MappedWords { map })
+    }
+
+    /// Writes words to a table at file_path, in the format read by open.
+    pub fn write<I: IntoIterator<Item = u64>>(file_path: &str, words: I) -> io::Result<()> {
+        let mut words: Vec<u64> = words.into_iter().collect();
+        words.sort_unstable();
+        words.dedup();
+
+        let mut writer = BufWriter::new(File::create(file_path)?);
+        writer.write_all(MAGIC)?;
+        for word in words {
+            writer.write_all(&word.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// The words in the table. The map starts on a page boundary and MAGIC is eight bytes
+    /// long, so the words are always aligned.
+    fn words(&self) -> &[u64] {
+        bytemuck::cast_slice(&self.map[MAGIC.len()..])
+    }
+
+    pub fn contains(&self, word: &u64) -> bool {
+        self.words().binary_search_by(|probe| u64::from_le(*probe).cmp(word)).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.words().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words().is_empty()
+    }
+
+    /// Iterates over the words in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        self.words().iter().map(|&word| u64::from_le(word))
+    }
+}
@@ -0,0 +1,122 @@
+//! Definitions and other details of words, read from an optional file kept next to a
+//! dictionary, which annotate the words found in a board.
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use fnv::FnvHashMap;
+use crate::alphabet::Alphabet;
+
+/// What is known about a word. Any of the fields may be missing from the metadata file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordInfo {
+    pub definition: Option<String>,
+    /// How common the word is, where 1 is the most common word.
+    pub frequency_rank: Option<u32>,
+    pub part_of_speech: Option<String>,
+}
+
+impl WordInfo {
+    /// Writes the fields which are present as JSON object members, each preceded by a comma,
+    /// so that they can follow the members of an object describing the word.
+    pub fn write_json_fields<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        if let Some(definition) = &self.definition {
+            write!(out, ", \"definition\": {}", json_string(definition))?;
+        }
+        if let Some(rank) = self.frequency_rank {
+            write!(out, ", \"frequency_rank\": {}", rank)?;
+        }
+        if let Some(part_of_speech) = &self.part_of_speech {
+            write!(out, ", \"part_of_speech\": {}", json_string(part_of_speech))?;
+        }
+        Ok(())
+    }
+}
+
+/// Quotes a string for JSON, escaping the characters which need it.
+pub fn json_string(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Details of words, keyed by the word packed using the dictionary's alphabet, so that words
+/// are matched however they are cased or accented.
+#[derive(Clone, Debug)]
+pub struct Metadata {
+    alphabet: Alphabet,
+    entries: FnvHashMap<u64, WordInfo>,
+}
+
+impl Metadata {
+    pub fn new(alphabet: Alphabet) -> Metadata {
+        Metadata { alphabet, entries: FnvHashMap::default() }
+    }
+
+    /// Reads a tab separated file with one word per line, as
+    ///
+    /// `WORD<TAB>FREQUENCY_RANK<TAB>PART_OF_SPEECH<TAB>DEFINITION`,
+    ///
+    /// where any field but the word may be left empty, and trailing fields may be left out.
+    /// Blank lines and lines starting with # are ignored, as are words outside of alphabet.
+    pub fn read(file_path: &str, alphabet: Alphabet) -> Result<Metadata, String> {
+        let file = File::open(file_path).map_err(|err| format!("Could not open {}: {}", file_path, err))?;
+        let mut metadata = Metadata::new(alphabet);
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|err| format!("Could not read {}: {}", file_path, err))?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split('\t').map(str::trim);
+            let word = fields.next().unwrap();
+            let mut field = || fields.next().filter(|field| !field.is_empty());
+            let frequency_rank = match field() {
+                Some(rank) => Some(rank.parse()
+                    .map_err(|_| format!("\"{}\" is not a frequency rank, in the line \"{}\".", rank, line))?),
+                None => None,
+            };
+            let part_of_speech = field().map(str::to_string);
+            let definition = field().map(str::to_string);
+
+            metadata.insert(word, WordInfo { definition, frequency_rank, part_of_speech });
+        }
+
+        Ok(metadata)
+    }
+
+    /// Adds the details of a word, replacing any previous details. Returns false, and does
+    /// nothing, if the word does not fit in the alphabet.
+    pub fn insert(&mut self, word: &str, info: WordInfo) -> bool {
+        match self.alphabet.encode(word) {
+            Some(packed) => {
+                self.entries.insert(packed, info);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get(&self, word: &str) -> Option<&WordInfo> {
+        self.entries.get(&self.alphabet.encode(word)?)
+    }
+
+    /// The number of words with details.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
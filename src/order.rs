@@ -0,0 +1,179 @@
+//! Orders and groupings of the found words, for scanning the list the way players do during a
+//! round: longest words first, or every word starting from the cell under a finger.
+use std::cmp::{Ordering, Reverse};
+use std::io::{self, Write};
+use std::str::FromStr;
+use crate::{Board, path_to_vec};
+
+/// A key which the found words can be sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    /// Highest score first.
+    Score,
+    /// Longest word first.
+    Length,
+    /// Alphabetical order of the words.
+    Alphabetical,
+    /// By the cell the path starts in, row by row from the top left.
+    StartCell,
+    /// Shortest swipe first, a diagonal move being longer than a straight one.
+    PathLength,
+}
+
+impl SortKey {
+    /// Reads a key from its name: score, length, alpha, start or path.
+    pub fn from_name(name: &str) -> Option<SortKey> {
+        match name {
+            "score" => Some(SortKey::Score),
+            "length" => Some(SortKey::Length),
+            "alpha" => Some(SortKey::Alphabetical),
+            "start" => Some(SortKey::StartCell),
+            "path" => Some(SortKey::PathLength),
+            _ => None,
+        }
+    }
+
+    fn compare(self, a: &(String, u16, u64), b: &(String, u16, u64)) -> Ordering {
+        match self {
+            SortKey::Score => b.1.cmp(&a.1),
+            SortKey::Length => b.0.chars().count().cmp(&a.0.chars().count()),
+            SortKey::Alphabetical => a.0.cmp(&b.0),
+            SortKey::StartCell => start_cell(a.2).cmp(&start_cell(b.2)),
+            SortKey::PathLength => swipe_length(a.2).total_cmp(&swipe_length(b.2)),
+        }
+    }
+}
+
+/// The cell, as (row, column), that a path starts in.
+fn start_cell(path: u64) -> (u8, u8) {
+    path_to_vec(path)[0]
+}
+
+/// The distance covered by a swipe along path, in cells.
+fn swipe_length(path: u64) -> f32 {
+    path_to_vec(path).windows(2)
+        .map(|step| if step[0].0 != step[1].0 && step[0].1 != step[1].1 { 2f32.sqrt() } else { 1.0 })
+        .sum()
+}
+
+/// An order of the found words, given by a list of keys, each breaking the ties of the keys before
+/// it. Any ties left are broken by score, then by word, then by path, so sorting is deterministic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortOrder {
+    keys: Vec<SortKey>,
+}
+
+impl SortOrder {
+    pub fn new(keys: Vec<SortKey>) -> SortOrder {
+        SortOrder { keys }
+    }
+
+    pub fn compare(&self, a: &(String, u16, u64), b: &(String, u16, u64)) -> Ordering {
+        self.keys.iter()
+            .fold(Ordering::Equal, |ordering, key| ordering.then_with(|| key.compare(a, b)))
+            .then_with(|| SortKey::Score.compare(a, b))
+            .then_with(|| a.0.cmp(&b.0))
+            .then_with(|| a.2.cmp(&b.2))
+    }
+}
+
+impl Default for SortOrder {
+    /// Highest score first.
+    fn default() -> SortOrder {
+        SortOrder::new(vec![SortKey::Score])
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    /// Parses a comma separated list of key names, eg. length,score.
+    fn from_str(keys: &str) -> Result<SortOrder, String> {
+        keys.split(',')
+            .map(|name| SortKey::from_name(name.trim()).ok_or(format!("{} is not a sort key.", name)))
+            .collect::<Result<Vec<SortKey>, String>>()
+            .map(SortOrder::new)
+    }
+}
+
+/// A labelled group of found words.
+pub type Group<'a> = (String, Vec<&'a (String, u16, u64)>);
+
+/// A way of splitting the found words into groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    /// By number of letters, longest words first.
+    Length,
+    /// By the cell the path starts in, row by row from the top left.
+    StartCell,
+}
+
+impl Grouping {
+    /// Reads a grouping from its name: length or start.
+    pub fn from_name(name: &str) -> Option<Grouping> {
+        match name {
+            "length" => Some(Grouping::Length),
+            "start" => Some(Grouping::StartCell),
+            _ => None,
+        }
+    }
+
+    /// Returns the key of the group an entry belongs to, in the order the groups are listed.
+    fn key(self, entry: &(String, u16, u64)) -> (Reverse<usize>, (u8, u8)) {
+        match self {
+            Grouping::Length => (Reverse(entry.0.chars().count()), (0, 0)),
+            Grouping::StartCell => (Reverse(0), start_cell(entry.2)),
+        }
+    }
+
+    fn label(self, entry: &(String, u16, u64)) -> String {
+        match self {
+            Grouping::Length => format!("{} letters", entry.0.chars().count()),
+            Grouping::StartCell => {
+                let (x, y) = start_cell(entry.2);
+                format!("Starting at ({}, {})", x, y)
+            }
+        }
+    }
+}
+
+impl Board {
+    /// Sorts the found words in the given order.
+    pub fn sort_entries_by(&mut self, order: &SortOrder) {
+        self.word_info.sort_by(|a, b| order.compare(a, b));
+    }
+
+    /// Splits the found words into labelled groups. The words of each group are kept in their
+    /// current order.
+    pub fn groups(&self, grouping: Grouping) -> Vec<Group<'_>> {
+        let mut entries: Vec<&(String, u16, u64)> = self.word_info.iter().collect();
+        entries.sort_by_key(|entry| grouping.key(entry));
+
+        let mut groups: Vec<Group> = Vec::new();
+        for entry in entries {
+            let label = grouping.label(entry);
+            match groups.last_mut() {
+                Some((last, group)) if *last == label => group.push(entry),
+                _ => groups.push((label, vec![entry])),
+            }
+        }
+        groups
+    }
+
+    /// Writes the found words to out in the format of words.txt, under a heading for each group.
+    pub fn write_groups<W: Write>(&self, out: &mut W, grouping: Grouping) -> io::Result<()> {
+        for (index, (label, entries)) in self.groups(grouping).iter().enumerate() {
+            if index > 0 {
+                writeln!(out)?;
+            }
+            writeln!(out, "{} ({} words)", label, entries.len())?;
+            for (word, score, path) in entries {
+                let cells: Vec<String> = path_to_vec(*path).iter()
+                    .map(|(x, y)| format!("({}, {})", x, y))
+                    .collect();
+                writeln!(out, "{}, {}, [{}]", word, score, cells.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
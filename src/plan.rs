@@ -0,0 +1,125 @@
+//! Chooses and orders the words to enter in a round, so that as many points as possible
+//! are scored before the round runs out of time.
+use std::str::FromStr;
+use crate::path_to_vec;
+
+/// The time, in seconds, which entering words takes.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeModel {
+    /// Time spent on each word, regardless of its length (eg. lifting the finger and
+    /// waiting for the word to be accepted).
+    pub per_word: f32,
+    /// Time spent swiping from one cell of a word to the next.
+    pub per_cell: f32,
+    /// Time spent moving, per cell of distance, from the last cell of a word to the
+    /// first cell of the next word.
+    pub per_move: f32,
+}
+
+impl Default for TimeModel {
+    fn default() -> TimeModel {
+        TimeModel { per_word: 0.5, per_cell: 0.15, per_move: 0.1 }
+    }
+}
+
+impl FromStr for TimeModel {
+    type Err = String;
+
+    /// Parses a time model of the form WORD,CELL,MOVE, eg. 0.5,0.15,0.1. The times can not be
+    /// negative, and entering a word has to take some time.
+    fn from_str(model: &str) -> Result<TimeModel, String> {
+        let invalid = || format!("\"{}\" is not a time model: it should be WORD,CELL,MOVE.", model.trim());
+        let times = model.split(',')
+            .map(|time| time.trim().parse::<f32>().map_err(|_| invalid()))
+            .collect::<Result<Vec<f32>, String>>()?;
+        if times.len() != 3 {
+            return Err(invalid());
+        }
+        let model = TimeModel { per_word: times[0], per_cell: times[1], per_move: times[2] };
+        if !times.iter().all(|time| time.is_finite() && *time >= 0.) || model.per_word + model.per_cell <= 0. {
+            return Err("The time model needs times of at least 0, and a word or a cell which takes some time.".to_string());
+        }
+        Ok(model)
+    }
+}
+
+impl TimeModel {
+    /// Returns the time taken to swipe path, not including the move to its first cell.
+    pub fn swipe_time(&self, path: &[(u8, u8)]) -> f32 {
+        self.per_word + self.per_cell * (path.len() - 1) as f32
+    }
+
+    /// Returns the time taken to move the finger between two cells.
+    pub fn move_time(&self, from: (u8, u8), to: (u8, u8)) -> f32 {
+        let dx = from.0 as f32 - to.0 as f32;
+        let dy = from.1 as f32 - to.1 as f32;
+        self.per_move * (dx * dx + dy * dy).sqrt()
+    }
+}
+
+/// A word in a play list, along with the time and score accumulated once it is entered.
+#[derive(Clone, Debug)]
+pub struct PlannedWord {
+    pub word: String,
+    pub score: u16,
+    pub path: u64,
+    pub cumulative_time: f32,
+    pub cumulative_score: u32,
+}
+
+/// Picks and orders words from word_info, which should hold each word once (see
+/// Board::dedup_entries), so that the play list fits in time_budget seconds.
+///
+/// Finding the best play list is a variant of the orienteering problem, so the planner is
+/// greedy: starting from wherever the previous word ended, it picks the word with the most
+/// points per second, counting the move to the word's first cell, until no word fits.
+pub fn plan_round(word_info: &[(String, u16, u64)], model: &TimeModel, time_budget: f32) -> Vec<PlannedWord> {
+    let paths: Vec<Vec<(u8, u8)>> = word_info.iter().map(|&(_, _, path)| path_to_vec(path)).collect();
+    let mut used = vec![false; word_info.len()];
+    let mut plan = Vec::new();
+    let mut position: Option<(u8, u8)> = None;
+    let mut elapsed = 0.;
+    let mut total_score = 0;
+
+    loop {
+        let mut best: Option<(usize, f32, f32)> = None;
+        for (index, (_, score, _)) in word_info.iter().enumerate() {
+            if used[index] {
+                continue;
+            }
+
+            let path = &paths[index];
+            let time = model.swipe_time(path) + position.map_or(0., |from| model.move_time(from, path[0]));
+            if elapsed + time > time_budget {
+                continue;
+            }
+
+            // A word which takes no time at all is always worth entering.
+            let rate = if time > 0. { *score as f32 / time } else { f32::INFINITY };
+            if best.is_none_or(|(_, best_rate, _)| rate > best_rate) {
+                best = Some((index, rate, time));
+            }
+        }
+
+        let (index, _, time) = match best {
+            Some(best) => best,
+            None => break,
+        };
+
+        used[index] = true;
+        elapsed += time;
+        total_score += word_info[index].1 as u32;
+        position = paths[index].last().copied();
+
+        let (word, score, path) = &word_info[index];
+        plan.push(PlannedWord {
+            word: word.clone(),
+            score: *score,
+            path: *path,
+            cumulative_time: elapsed,
+            cumulative_score: total_score,
+        });
+    }
+
+    plan
+}
@@ -0,0 +1,193 @@
+//! An interactive session which loads the dictionary once, then solves each board typed at a
+//! prompt and answers questions about its words.
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use unicode_normalization::UnicodeNormalization;
+use crate::{Board, BOARD_SIZE, try_parse_board_and_mults};
+use crate::dictionary::Dictionary;
+use crate::filter::Filter;
+use crate::order::SortOrder;
+use crate::pretty::{PrettyStyle, render_path};
+
+const TILES: usize = BOARD_SIZE * BOARD_SIZE;
+
+const HELP: &str = "\
+Commands:
+  find WORD     shows whether WORD is in the board, and how to swipe it
+  top [N]       lists the N (10 by default) best words matching the filter
+  filter EXPR   only lists the words matching EXPR, eg. len>=6 word=*ING
+  filter        lists every word again
+  next board    reads a new board
+  help          shows this list
+  quit          ends the session
+";
+
+/// What the prompt does after a command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    NextBoard,
+    Quit,
+}
+
+/// A dictionary along with the board being played, if one has been typed yet.
+pub struct Session {
+    dictionary: Arc<Dictionary>,
+    board: Option<Board>,
+    filter: Filter,
+    style: PrettyStyle,
+}
+
+/// Counts the symbols of a board as typed, in the way try_parse_board_and_mults does.
+fn count_symbols(raw: &str) -> usize {
+    raw.nfc().filter(|c| !c.is_whitespace()).count()
+}
+
+impl Session {
+    /// Starts a session with no board, drawing swipes in the given style.
+    pub fn new(dictionary: Arc<Dictionary>, style: PrettyStyle) -> Session {
+        Session { dictionary, board: None, filter: Filter::new(), style }
+    }
+
+    /// Solves a board written as in board.txt, replacing the current board and clearing the
+    /// filter. The multipliers may be left out, for a board without any. Returns the number of
+    /// distinct words found.
+    pub fn load_board(&mut self, raw: &str) -> Result<usize, String> {
+        let mut lines = vec![raw.to_string()];
+        if count_symbols(raw) == TILES {
+            lines.push("-".repeat(TILES));
+        }
+        let (board, word_int_mults, letter_mults) = try_parse_board_and_mults(lines, &self.dictionary.alphabet)?;
+
+        let mut board = Board::new(board, word_int_mults, letter_mults, Arc::clone(&self.dictionary));
+        board.solve();
+        board.dedup_entries();
+        board.sort_entries_by(&SortOrder::default());
+        let count = board.word_info.len();
+        self.board = Some(board);
+        self.filter = Filter::new();
+        Ok(count)
+    }
+
+    pub fn board(&self) -> Option<&Board> {
+        self.board.as_ref()
+    }
+
+    /// Returns the distinct words of the board which match the filter, best first.
+    pub fn words(&self) -> Vec<&(String, u16, u64)> {
+        match &self.board {
+            Some(board) => board.word_info.iter()
+                .filter(|(word, score, path)| self.filter.matches(board, word, *score, *path))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Runs one command, writing its answer to out.
+    pub fn execute<W: Write>(&mut self, command: &str, out: &mut W) -> io::Result<Flow> {
+        let (name, argument) = command.trim().split_once(' ').unwrap_or((command.trim(), ""));
+        let argument = argument.trim();
+        match name {
+            "" => {}
+            "help" => write!(out, "{}", HELP)?,
+            "quit" | "exit" => return Ok(Flow::Quit),
+            "next" if argument.is_empty() || argument == "board" => return Ok(Flow::NextBoard),
+            "find" if !argument.is_empty() => self.find(&argument.to_uppercase(), out)?,
+            "top" => match if argument.is_empty() { Ok(10) } else { argument.parse::<usize>() } {
+                Ok(n) => self.top(n, out)?,
+                Err(_) => writeln!(out, "top expects a number of words.")?,
+            },
+            "filter" => match argument.parse::<Filter>() {
+                Ok(filter) => {
+                    self.filter = filter;
+                    writeln!(out, "{} words match the filter.", self.words().len())?;
+                }
+                Err(err) => writeln!(out, "{}", err)?,
+            },
+            _ => writeln!(out, "{} is not a command. Type help for the list of commands.", command.trim())?,
+        }
+        Ok(Flow::Continue)
+    }
+
+    fn find<W: Write>(&self, word: &str, out: &mut W) -> io::Result<()> {
+        let Some(board) = &self.board else {
+            return writeln!(out, "There is no board yet.");
+        };
+        match board.word_info.iter().find(|(found, _, _)| found == word) {
+            Some((_, score, path)) => {
+                writeln!(out, "{} scores {} points:", word, score)?;
+                write!(out, "{}", render_path(board, *path, self.style))
+            }
+            None => match self.dictionary.alphabet.encode(word) {
+                Some(encoded) if self.dictionary.store.is_word(encoded) =>
+                    writeln!(out, "{} is a word, but it is not in the board.", word),
+                _ => writeln!(out, "{} is not in the {} dictionary.", word, self.dictionary.name),
+            },
+        }
+    }
+
+    fn top<W: Write>(&self, n: usize, out: &mut W) -> io::Result<()> {
+        for (rank, (word, score, _)) in self.words().into_iter().take(n).enumerate() {
+            writeln!(out, "{:>3}. {:<12} {:>4}", rank + 1, word, score)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the lines of a board from input until all of its letters, and then either all of its
+/// multipliers or an empty line, have been typed. Returns None at the end of input.
+fn read_board<R: BufRead, W: Write>(lines: &mut io::Lines<R>, out: &mut W) -> io::Result<Option<String>> {
+    let mut raw = String::new();
+    write!(out, "board> ")?;
+    loop {
+        out.flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(None);
+        };
+        let line = line?;
+        let symbols = count_symbols(&raw);
+        if line.trim().is_empty() && symbols >= TILES {
+            return Ok(Some(raw));
+        }
+        raw.push_str(&line);
+        raw.push('\n');
+
+        match count_symbols(&raw) {
+            symbols if symbols >= 2 * TILES => return Ok(Some(raw)),
+            symbols if symbols >= TILES => write!(out, "multipliers (or an empty line for none)> ")?,
+            _ => write!(out, "board> ")?,
+        }
+    }
+}
+
+/// Runs the prompt until the player quits or input ends: a board is read and solved, then
+/// commands are answered until the player asks for the next board.
+pub fn run_repl<R: BufRead, W: Write>(session: &mut Session, input: R, out: &mut W) -> io::Result<()> {
+    let mut lines = input.lines();
+    writeln!(out, "Type a board: its {} letters, then its multipliers as in board.txt.", TILES)?;
+    loop {
+        let Some(raw) = read_board(&mut lines, out)? else {
+            return Ok(());
+        };
+        match session.load_board(&raw) {
+            Ok(count) => writeln!(out, "{} words were found. Type help for the list of commands.", count)?,
+            Err(err) => {
+                writeln!(out, "{}", err)?;
+                continue;
+            }
+        }
+
+        loop {
+            write!(out, "> ")?;
+            out.flush()?;
+            let Some(line) = lines.next() else {
+                return Ok(());
+            };
+            match session.execute(&line?, out)? {
+                Flow::Continue => {}
+                Flow::NextBoard => break,
+                Flow::Quit => return Ok(()),
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Draws the swipe of each word on a picture of the board in the terminal, which is far easier
+//! to follow than a list of coordinates.
+use std::io::{self, Write};
+use crate::{Board, BOARD_SIZE, path_to_vec};
+
+const RESET: &str = "\x1b[0m";
+const START: &str = "\x1b[1;32m";
+const STEP: &str = "\x1b[1;33m";
+const OFF_PATH: &str = "\x1b[2m";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrettyStyle {
+    /// Plain text, for files and terminals without colours.
+    Plain,
+    /// ANSI colours: the first cell of the swipe in green, the rest of it in yellow, the cells
+    /// off the swipe dimmed, and each multiplier in its own colour.
+    Colour,
+}
+
+impl PrettyStyle {
+    pub fn from_name(name: &str) -> Option<PrettyStyle> {
+        match name {
+            "plain" => Some(PrettyStyle::Plain),
+            "colour" | "color" => Some(PrettyStyle::Colour),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the abbreviation of the multiplier on the tile at vertex, and the colour it is
+/// drawn in, if the tile has one.
+fn multiplier_tag(board: &Board, vertex: usize) -> Option<(&'static str, &'static str)> {
+    match (board.letter_mults[vertex], board.word_int_mults[vertex]) {
+        (_, 3) => Some(("TW", "\x1b[31m")),
+        (_, 2) => Some(("DW", "\x1b[35m")),
+        (3, _) => Some(("TL", "\x1b[34m")),
+        (2, _) => Some(("DL", "\x1b[36m")),
+        _ => None,
+    }
+}
+
+fn paint(text: &str, colour: &str, style: PrettyStyle) -> String {
+    match style {
+        PrettyStyle::Plain => text.to_string(),
+        PrettyStyle::Colour => format!("{}{}{}", colour, text, RESET),
+    }
+}
+
+/// Draws the board with the cells of path numbered in the order they are swiped, and the
+/// multiplier of each tile below its letter:
+///
+/// ```text
+/// +------+------+------+------+
+/// | H  1 | U  2 | N  3 | T  4 |
+/// |      |      |      |   TW |
+/// +------+------+------+------+
+/// ```
+pub fn render_path(board: &Board, path: u64, style: PrettyStyle) -> String {
+    let mut steps = [0; BOARD_SIZE * BOARD_SIZE];
+    for (step, (x, y)) in path_to_vec(path).into_iter().enumerate() {
+        steps[x as usize * BOARD_SIZE + y as usize] = step + 1;
+    }
+
+    let border = format!("+{}\n", "------+".repeat(BOARD_SIZE));
+    let mut picture = border.clone();
+    for x in 0..BOARD_SIZE {
+        let mut letters = String::from("|");
+        let mut tags = String::from("|");
+        for y in 0..BOARD_SIZE {
+            let vertex = x * BOARD_SIZE + y;
+            let letter = board.alphabet().symbol(board.board[vertex]);
+            let (text, colour) = match steps[vertex] {
+                0 => (format!(" {}    ", letter), OFF_PATH),
+                1 => (format!(" {} {:>2} ", letter, 1), START),
+                step => (format!(" {} {:>2} ", letter, step), STEP),
+            };
+            letters.push_str(&paint(&text, colour, style));
+            letters.push('|');
+
+            match multiplier_tag(board, vertex) {
+                Some((tag, colour)) => tags.push_str(&paint(&format!("   {} ", tag), colour, style)),
+                None => tags.push_str("      "),
+            }
+            tags.push('|');
+        }
+        picture.push_str(&letters);
+        picture.push('\n');
+        picture.push_str(&tags);
+        picture.push('\n');
+        picture.push_str(&border);
+    }
+    picture
+}
+
+/// Writes each word and its score, followed by the board with the word's swipe drawn on it.
+pub fn write_pretty<W: Write>(out: &mut W, board: &Board, words: &[(String, u16, u64)],
+                              style: PrettyStyle) -> io::Result<()> {
+    for (word, score, path) in words {
+        writeln!(out, "{} ({} points)", word, score)?;
+        writeln!(out, "{}", render_path(board, *path, style))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! A small seeded random number generator, so that simulations can be repeated exactly, and
+//! boards of random letters and multipliers drawn with it.
+use crate::{BOARD_SIZE, Tiles};
+use crate::alphabet::Alphabet;
+
+/// A splitmix64 generator: fast, and good enough for drawing boards and simulating players.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+/// Scrambles the bits of value, so that close values give unrelated results.
+pub fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        mix(self.state)
+    }
+
+    /// Returns a number from 0 up to, but not including, 1.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns a number from 0 up to, but not including, n, which must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// The number of tiles of a board carrying each kind of multiplier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiplierCounts {
+    pub double_letter: usize,
+    pub triple_letter: usize,
+    pub double_word: usize,
+    pub triple_word: usize,
+}
+
+impl MultiplierCounts {
+    /// A board without any multipliers.
+    pub const NONE: MultiplierCounts = MultiplierCounts { double_letter: 0, triple_letter: 0, double_word: 0, triple_word: 0 };
+}
+
+impl Default for MultiplierCounts {
+    fn default() -> MultiplierCounts {
+        MultiplierCounts { double_letter: 2, triple_letter: 1, double_word: 1, triple_word: 1 }
+    }
+}
+
+/// Draws a board of letters from alphabet, with the multipliers placed on distinct tiles.
+/// Letters are drawn with weights falling with the square of their value, so that common
+/// letters, which are worth the least, turn up the most. Returns the letters, word multipliers
+/// and letter multipliers, as parse_board_and_mults does.
+pub fn random_board(alphabet: &Alphabet, counts: &MultiplierCounts, rng: &mut Rng) -> (Tiles, Tiles, Tiles) {
+    let weights: Vec<f32> = (1..=alphabet.len() as u8)
+        .map(|code| 1. / (alphabet.value(code).max(1) as f32).powi(2))
+        .collect();
+    let total: f32 = weights.iter().sum();
+
+    let mut board = [0; BOARD_SIZE * BOARD_SIZE];
+    for tile in board.iter_mut() {
+        let mut draw = rng.next_f32() * total;
+        *tile = alphabet.len() as u8;
+        for (index, weight) in weights.iter().enumerate() {
+            if draw < *weight {
+                *tile = index as u8 + 1;
+                break;
+            }
+            draw -= weight;
+        }
+    }
+
+    // Shuffles the tiles, then hands out multipliers from the front.
+    let mut tiles: Vec<usize> = (0..BOARD_SIZE * BOARD_SIZE).collect();
+    for index in (1..tiles.len()).rev() {
+        tiles.swap(index, rng.below(index + 1));
+    }
+    let mut word_int_mults = [1; BOARD_SIZE * BOARD_SIZE];
+    let mut letter_mults = [1; BOARD_SIZE * BOARD_SIZE];
+    let mut tiles = tiles.into_iter();
+    for (count, is_word, mult) in [(counts.triple_word, true, 3), (counts.double_word, true, 2),
+                                   (counts.triple_letter, false, 3), (counts.double_letter, false, 2)] {
+        for tile in tiles.by_ref().take(count) {
+            if is_word { word_int_mults[tile] = mult } else { letter_mults[tile] = mult }
+        }
+    }
+
+    (board, word_int_mults, letter_mults)
+}
@@ -0,0 +1,281 @@
+//! Reads the board from a screenshot of the game, recognising the letters by matching them
+//! against bundled glyph templates, and the multipliers by the colour of their tiles.
+use std::fs::File;
+use std::io;
+use crate::BOARD_SIZE;
+use crate::alphabet::Alphabet;
+
+// Glyphs are compared after being cropped to their ink and resampled to this many cells.
+const GRID_WIDTH: usize = 10;
+const GRID_HEIGHT: usize = 14;
+
+// The fraction of a cell's width which is ignored on each side when looking for the letter,
+// so that the tile's border and any multiplier badge in its corner are not mistaken for ink.
+const LETTER_INSET: f32 = 0.18;
+// The fraction of a cell's width from its edge at which the tile's colour is sampled.
+const COLOUR_INSET: f32 = 0.1;
+
+// Cells smaller than this many pixels on either side are too small to read a letter from.
+const MIN_CELL_SIZE: u32 = 16;
+
+// Glyphs whose average difference per grid cell is above this are not considered letters.
+const MAX_GLYPH_DISTANCE: f32 = 0.35;
+
+/// A bitmap of each letter of the built in alphabets, as the ink of the letter in DejaVu Sans
+/// Bold covers a GRID_WIDTH by GRID_HEIGHT grid, one row per u16 with the leftmost cell in
+/// bit 9. They are made by tests/fixtures/make_screenshots.py.
+const GLYPHS: [(char, [u16; GRID_HEIGHT]); 31] = [
+    ('A', [0b0001111000, 0b0001111000, 0b0001111000, 0b0001111000, 0b0011111100, 0b0011001100, 0b0011001100, 0b0011001100, 0b0111001110, 0b0111111110, 0b0111111110, 0b0111111110, 0b1110000111, 0b1110000111]),
+    ('B', [0b1111111100, 0b1111111110, 0b1111111110, 0b1110001111, 0b1110001110, 0b1111111110, 0b1111111100, 0b1111111110, 0b1110001111, 0b1110000111, 0b1110000111, 0b1111111111, 0b1111111110, 0b1111111100]),
+    ('C', [0b0000111110, 0b0011111111, 0b0111111111, 0b0111000000, 0b1111000000, 0b1110000000, 0b1110000000, 0b1110000000, 0b1110000000, 0b1111000000, 0b0111000000, 0b0111111111, 0b0011111111, 0b0000111110]),
+    ('D', [0b1111111000, 0b1111111100, 0b1111111110, 0b1110001110, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110001110, 0b1111111110, 0b1111111100, 0b1111111000]),
+    ('E', [0b1111111111, 0b1111111111, 0b1111111111, 0b1111000000, 0b1111000000, 0b1111111110, 0b1111111110, 0b1111111110, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111111111, 0b1111111111, 0b1111111111]),
+    ('F', [0b1111111111, 0b1111111111, 0b1111111111, 0b1111000000, 0b1111000000, 0b1111111110, 0b1111111111, 0b1111111111, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000]),
+    ('G', [0b0001111110, 0b0011111111, 0b0111111111, 0b0111000000, 0b1110000000, 0b1110000000, 0b1110001111, 0b1110001111, 0b1110001111, 0b1110000111, 0b0111000111, 0b0111111111, 0b0011111111, 0b0001111100]),
+    ('H', [0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111]),
+    ('I', [0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111, 0b1111111111]),
+    ('J', [0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0000111111, 0b0001111111, 0b1111111110, 0b1111110000]),
+    ('K', [0b1110001110, 0b1110011100, 0b1110011100, 0b1110111000, 0b1111110000, 0b1111100000, 0b1111100000, 0b1111100000, 0b1111110000, 0b1111111000, 0b1110111000, 0b1110011100, 0b1110001110, 0b1110001111]),
+    ('L', [0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111000000, 0b1111111111, 0b1111111111, 0b1111111111]),
+    ('M', [0b1110000111, 0b1110000111, 0b1111001111, 0b1111001111, 0b1111001111, 0b1111001111, 0b1101111011, 0b1101111011, 0b1101110011, 0b1100110011, 0b1100110011, 0b1100000011, 0b1100000011, 0b1100000011]),
+    ('N', [0b1110000111, 0b1111000111, 0b1111000111, 0b1111100111, 0b1111100111, 0b1111110111, 0b1110110111, 0b1110110111, 0b1110111111, 0b1110011111, 0b1110011111, 0b1110001111, 0b1110001111, 0b1110000111]),
+    ('O', [0b0001111000, 0b0011111100, 0b0111111110, 0b0110000110, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b0110000110, 0b0111111110, 0b0011111100, 0b0001111000]),
+    ('P', [0b1111111100, 0b1111111110, 0b1111111111, 0b1110001111, 0b1110000111, 0b1110000111, 0b1111111111, 0b1111111110, 0b1111111100, 0b1110000000, 0b1110000000, 0b1110000000, 0b1110000000, 0b1110000000]),
+    ('Q', [0b0001111000, 0b0111111110, 0b0111001110, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b0111001110, 0b0011111100, 0b0001111100, 0b0000001100, 0b0000000110]),
+    ('R', [0b1111111000, 0b1111111100, 0b1111111100, 0b1110001110, 0b1110001110, 0b1110011100, 0b1111111100, 0b1111111000, 0b1111111100, 0b1110011100, 0b1110001110, 0b1110001110, 0b1110001110, 0b1110000111]),
+    ('S', [0b0011111110, 0b0111111110, 0b1111111110, 0b1110000000, 0b1110000000, 0b1111110000, 0b0111111100, 0b0011111110, 0b0000011111, 0b0000000111, 0b1000000111, 0b1111111111, 0b1111111110, 0b0011111100]),
+    ('T', [0b1111111111, 0b1111111111, 0b1111111111, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000]),
+    ('U', [0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b0111111110, 0b0111111110, 0b0001111000]),
+    ('V', [0b1110000111, 0b1110000111, 0b0110000110, 0b0110000110, 0b0111001110, 0b0111001110, 0b0011001100, 0b0011001100, 0b0011111100, 0b0011111100, 0b0001111000, 0b0001111000, 0b0001111000, 0b0001111000]),
+    ('W', [0b1100110011, 0b1100110011, 0b1100110011, 0b1100110011, 0b0100110010, 0b0101111010, 0b0111111010, 0b0111111110, 0b0111001110, 0b0111001110, 0b0111001110, 0b0111001110, 0b0111001110, 0b0011001100]),
+    ('X', [0b1110000111, 0b0111001110, 0b0111001110, 0b0011111100, 0b0011111100, 0b0001111000, 0b0001111000, 0b0001111000, 0b0001111000, 0b0011111100, 0b0011001100, 0b0111001110, 0b0110000110, 0b1110000111]),
+    ('Y', [0b1110000111, 0b0110000110, 0b0111001110, 0b0011001100, 0b0011111100, 0b0001111000, 0b0001111000, 0b0001111000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000, 0b0000110000]),
+    ('Z', [0b1111111111, 0b1111111111, 0b1111111111, 0b0000001110, 0b0000011100, 0b0000111100, 0b0000111000, 0b0001110000, 0b0011110000, 0b0011100000, 0b0111000000, 0b1111111111, 0b1111111111, 0b1111111111]),
+    ('Ä', [0b0001011000, 0b0001001000, 0b0000000000, 0b0001111000, 0b0001111000, 0b0001111000, 0b0011111100, 0b0011001100, 0b0011001100, 0b0111001110, 0b0111111110, 0b0111111110, 0b1110000111, 0b1110000111]),
+    ('Å', [0b0000110000, 0b0001001000, 0b0001001000, 0b0001111000, 0b0001111000, 0b0001111000, 0b0011111100, 0b0011001100, 0b0011001100, 0b0111001110, 0b0111111110, 0b0111111110, 0b1110000111, 0b1110000111]),
+    ('Ñ', [0b0001101000, 0b0010011000, 0b0000000000, 0b1110000111, 0b1111000111, 0b1111100111, 0b1111100111, 0b1111110111, 0b1110110111, 0b1110111111, 0b1110011111, 0b1110011111, 0b1110001111, 0b1110001111]),
+    ('Ö', [0b0001111000, 0b0001001000, 0b0000000000, 0b0011111100, 0b0111111110, 0b1110000110, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b0111001110, 0b0111111110, 0b0001111000]),
+    ('Ü', [0b0001011000, 0b0001001000, 0b0000000000, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1110000111, 0b1111001111, 0b0111111110, 0b0011111100]),
+];
+
+/// An RGB image, stored row by row.
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        self.pixels[(y * self.width + x) as usize]
+    }
+}
+
+/// Reads the PNG file at file_path, dropping any alpha channel.
+pub fn read_png(file_path: &str) -> io::Result<Image> {
+    let mut decoder = png::Decoder::new(File::open(file_path)?);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(io::Error::other)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(io::Error::other)?;
+
+    let channels = info.color_type.samples();
+    let pixels = buf[..info.buffer_size()].chunks_exact(channels).map(|pixel| match channels {
+        1 | 2 => [pixel[0]; 3],
+        _ => [pixel[0], pixel[1], pixel[2]],
+    }).collect();
+
+    Ok(Image { width: info.width, height: info.height, pixels })
+}
+
+/// The part of a screenshot which holds the board, in pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The colours of each kind of tile, used to recognise the multipliers.
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    pub plain: [u8; 3],
+    pub double_letter: [u8; 3],
+    pub triple_letter: [u8; 3],
+    pub double_word: [u8; 3],
+    pub triple_word: [u8; 3],
+}
+
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette {
+            plain: [240, 230, 210],
+            double_letter: [90, 180, 90],
+            triple_letter: [60, 120, 200],
+            double_word: [240, 170, 50],
+            triple_word: [210, 60, 60],
+        }
+    }
+}
+
+impl Palette {
+    /// Returns the board symbol of the tile whose colour is closest to colour.
+    fn closest_symbol(&self, colour: [u8; 3]) -> char {
+        let distance = |other: [u8; 3]| -> u32 {
+            colour.iter().zip(&other).map(|(&a, &b)| (a as i32 - b as i32).pow(2) as u32).sum()
+        };
+
+        [(self.plain, '-'), (self.double_letter, 'D'), (self.triple_letter, 'T'),
+            (self.double_word, '2'), (self.triple_word, '3')]
+            .iter()
+            .min_by_key(|(tile, _)| distance(*tile))
+            .unwrap().1
+    }
+}
+
+/// Returns the relative luminance of a colour, from 0 to 255.
+fn luminance([r, g, b]: [u8; 3]) -> f32 {
+    0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32
+}
+
+/// Crops the ink in mask (of the given width) to its bounding box, and returns the fraction of
+/// ink in each cell of a GRID_WIDTH by GRID_HEIGHT grid laid over the box.
+fn glyph_features(mask: &[bool], width: usize) -> Option<Vec<f32>> {
+    let height = mask.len().checked_div(width)?;
+    let ink = |x: usize, y: usize| mask[y * width + x];
+
+    let xs: Vec<usize> = (0..width).filter(|&x| (0..height).any(|y| ink(x, y))).collect();
+    let ys: Vec<usize> = (0..height).filter(|&y| (0..width).any(|x| ink(x, y))).collect();
+    let (&left, &right) = (xs.first()?, xs.last()?);
+    let (&top, &bottom) = (ys.first()?, ys.last()?);
+    let (box_width, box_height) = ((right - left + 1) as f32, (bottom - top + 1) as f32);
+
+    let mut features = Vec::with_capacity(GRID_WIDTH * GRID_HEIGHT);
+    for gy in 0..GRID_HEIGHT {
+        let y0 = top + (gy as f32 * box_height / GRID_HEIGHT as f32) as usize;
+        let y1 = (top + ((gy + 1) as f32 * box_height / GRID_HEIGHT as f32).ceil() as usize).max(y0 + 1);
+        for gx in 0..GRID_WIDTH {
+            let x0 = left + (gx as f32 * box_width / GRID_WIDTH as f32) as usize;
+            let x1 = (left + ((gx + 1) as f32 * box_width / GRID_WIDTH as f32).ceil() as usize).max(x0 + 1);
+            let inked = (y0..y1).flat_map(|y| (x0..x1).map(move |x| (x, y)))
+                .filter(|&(x, y)| ink(x, y)).count();
+            features.push(inked as f32 / ((y1 - y0) * (x1 - x0)) as f32);
+        }
+    }
+
+    Some(features)
+}
+
+/// The letter glyphs which cells are matched against.
+pub struct Templates {
+    glyphs: Vec<(char, Vec<f32>)>,
+}
+
+impl Templates {
+    /// The templates bundled with the solver, for every letter of the built in alphabets.
+    pub fn bundled() -> Templates {
+        let glyphs = GLYPHS.iter().map(|(letter, rows)| {
+            let mask: Vec<bool> = rows.iter()
+                .flat_map(|row| (0..GRID_WIDTH).rev().map(move |bit| (row >> bit) & 1 == 1))
+                .collect();
+            (*letter, glyph_features(&mask, GRID_WIDTH).unwrap())
+        }).collect();
+        Templates { glyphs }
+    }
+
+    /// The bundled templates of the letters of alphabet, so that cells are only recognised as
+    /// letters of the alphabet. Alphabets with letters which have no template are an error.
+    pub fn for_alphabet(alphabet: &Alphabet) -> Result<Templates, String> {
+        let mut glyphs = Templates::bundled().glyphs;
+        let missing: Vec<char> = (1..=alphabet.len() as u8)
+            .map(|code| alphabet.symbol(code))
+            .filter(|letter| !glyphs.iter().any(|(glyph, _)| glyph == letter))
+            .collect();
+        if !missing.is_empty() {
+            let missing: Vec<String> = missing.iter().map(char::to_string).collect();
+            return Err(format!("Letters of the {} alphabet can not be read from screenshots: {}.",
+                               alphabet.name(), missing.join(", ")));
+        }
+        glyphs.retain(|(letter, _)| alphabet.code(*letter).is_some());
+        Ok(Templates { glyphs })
+    }
+
+    /// Returns the letter whose template is closest to the ink in mask, if any is close enough.
+    fn recognise(&self, mask: &[bool], width: usize) -> Option<char> {
+        let features = glyph_features(mask, width)?;
+        let (letter, distance) = self.glyphs.iter()
+            .map(|(letter, template)| {
+                let distance: f32 = template.iter().zip(&features).map(|(a, b)| (a - b).abs()).sum();
+                (*letter, distance / features.len() as f32)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+        if distance <= MAX_GLYPH_DISTANCE {
+            Some(letter)
+        } else {
+            None
+        }
+    }
+}
+
+/// Reads the board in region of image, returning its lines in the same form as the board file,
+/// which parse_board_and_mults accepts: the letters, a blank line, then the multipliers.
+pub fn read_board_image(image: &Image, region: &Region, palette: &Palette,
+                        templates: &Templates) -> Result<Vec<String>, String> {
+    let fits = |start: u32, length: u32, size: u32| start.checked_add(length).is_some_and(|end| end <= size);
+    if !fits(region.x, region.width, image.width) || !fits(region.y, region.height, image.height) {
+        return Err(format!("The board region does not fit in the {}x{} image.", image.width, image.height));
+    }
+
+    let cell_width = region.width / BOARD_SIZE as u32;
+    let cell_height = region.height / BOARD_SIZE as u32;
+    if cell_width < MIN_CELL_SIZE || cell_height < MIN_CELL_SIZE {
+        return Err(format!("The board region is too small: each tile must be at least {}x{} pixels.",
+                           MIN_CELL_SIZE, MIN_CELL_SIZE));
+    }
+    let mut letters = vec![String::new(); BOARD_SIZE];
+    let mut mults = vec![String::new(); BOARD_SIZE];
+
+    for row in 0..BOARD_SIZE {
+        for col in 0..BOARD_SIZE {
+            let left = region.x + col as u32 * cell_width;
+            let top = region.y + row as u32 * cell_height;
+
+            // The tile's colour is the average of samples near each of its corners.
+            let (dx, dy) = ((cell_width as f32 * COLOUR_INSET) as u32, (cell_height as f32 * COLOUR_INSET) as u32);
+            let (right, bottom) = (left + (cell_width - 1).saturating_sub(dx), top + (cell_height - 1).saturating_sub(dy));
+            let samples = [
+                image.pixel(left + dx, top + dy),
+                image.pixel(right, top + dy),
+                image.pixel(left + dx, bottom),
+                image.pixel(right, bottom),
+            ];
+            let mut tile = [0; 3];
+            for (channel, value) in tile.iter_mut().enumerate() {
+                *value = (samples.iter().map(|sample| sample[channel] as u32).sum::<u32>() / 4) as u8;
+            }
+
+            // Letters are drawn in a dark colour over the tile.
+            let threshold = luminance(tile) / 2.;
+            let (ix, iy) = ((cell_width as f32 * LETTER_INSET) as u32, (cell_height as f32 * LETTER_INSET) as u32);
+            let width = cell_width.saturating_sub(2 * ix) as usize;
+            let mask: Vec<bool> = (top + iy..top + cell_height - iy)
+                .flat_map(|y| (left + ix..left + cell_width - ix).map(move |x| (x, y)))
+                .map(|(x, y)| luminance(image.pixel(x, y)) < threshold)
+                .collect();
+
+            let letter = templates.recognise(&mask, width)
+                .ok_or_else(|| format!("No letter was recognised in cell ({}, {}).", row, col))?;
+
+            let separator = if col == 0 { "" } else { " " };
+            letters[row].push_str(separator);
+            letters[row].push(letter);
+            mults[row].push_str(separator);
+            mults[row].push(palette.closest_symbol(tile));
+        }
+    }
+
+    letters.push(String::new());
+    letters.append(&mut mults);
+    Ok(letters)
+}
@@ -0,0 +1,188 @@
+//! Simulated rounds between computer players of different strengths, for testing the balance
+//! of the game over many random boards.
+use std::hash::Hasher;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+use fnv::FnvHasher;
+use crate::{Board, path_to_vec};
+use crate::dictionary::Dictionary;
+use crate::plan::TimeModel;
+use crate::random::{MultiplierCounts, Rng, mix, random_board};
+
+/// A computer player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bot {
+    pub name: String,
+    /// The share of the dictionary the bot knows, from 0 to 1.
+    pub coverage: f32,
+    /// How fast the bot swipes, compared to the time model: 2 takes half the time.
+    pub speed: f32,
+    /// From -1, for a bot which goes for short words first, to 1, for one which goes for long
+    /// words first.
+    pub length_bias: f32,
+}
+
+impl Bot {
+    pub fn new(name: &str, coverage: f32, speed: f32, length_bias: f32) -> Bot {
+        Bot { name: name.to_string(), coverage, speed, length_bias }
+    }
+
+    /// Whether the bot knows word. Each bot knows its own share of the dictionary, picked by
+    /// hashing the word along with the bot's name, so it knows the same words on every board.
+    pub fn knows(&self, word: &str) -> bool {
+        let mut hasher = FnvHasher::default();
+        hasher.write(self.name.as_bytes());
+        hasher.write_u8(0);
+        hasher.write(word.as_bytes());
+        let draw = (mix(hasher.finish()) >> 40) as f32 / (1u64 << 24) as f32;
+        draw < self.coverage
+    }
+
+    /// Plays a round of round_time seconds on the distinct words of a solved board: the known
+    /// words are entered in the order the bot spots them, which favours long or short words as
+    /// given by its bias, for as long as time allows. Returns the words entered, with their
+    /// scores.
+    pub fn play(&self, word_info: &[(String, u16, u64)], model: &TimeModel, round_time: f32,
+                rng: &mut Rng) -> Vec<(String, u16)> {
+        let mut spotted: Vec<(f32, &(String, u16, u64))> = word_info.iter()
+            .filter(|(word, _, _)| self.knows(word))
+            .map(|entry| {
+                let len = entry.0.chars().count() as f32;
+                (len.powf(4. * self.length_bias) * (0.5 + rng.next_f32()), entry)
+            })
+            .collect();
+        spotted.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut played = Vec::new();
+        let mut position = None;
+        let mut elapsed = 0.;
+        for (_, (word, score, path)) in spotted {
+            let path = path_to_vec(*path);
+            let time = (model.swipe_time(&path) + position.map_or(0., |from| model.move_time(from, path[0]))) / self.speed;
+            if elapsed + time > round_time {
+                continue;
+            }
+            elapsed += time;
+            position = path.last().copied();
+            played.push((word.clone(), *score));
+        }
+        played
+    }
+}
+
+impl FromStr for Bot {
+    type Err = String;
+
+    /// Parses a bot of the form NAME:COVERAGE:SPEED:BIAS, eg. expert:0.9:1.5:0.5.
+    fn from_str(spec: &str) -> Result<Bot, String> {
+        let fields: Vec<&str> = spec.trim().split(':').collect();
+        let invalid = || format!("\"{}\" is not a bot: it should be NAME:COVERAGE:SPEED:BIAS.", spec.trim());
+        if fields.len() != 4 || fields[0].is_empty() {
+            return Err(invalid());
+        }
+        let parse = |field: &str| field.parse::<f32>().map_err(|_| invalid());
+        let bot = Bot::new(fields[0], parse(fields[1])?, parse(fields[2])?, parse(fields[3])?);
+        if !(0. ..=1.).contains(&bot.coverage) || bot.speed <= 0. || !(-1. ..=1.).contains(&bot.length_bias) {
+            return Err(format!("The bot {} needs a coverage from 0 to 1, a speed above 0 and a bias from -1 to 1.", bot.name));
+        }
+        Ok(bot)
+    }
+}
+
+/// The scores of every bot on one board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundResult {
+    /// The letters of the board, row by row.
+    pub board: String,
+    /// The score of each bot, in the order the bots were given.
+    pub scores: Vec<u32>,
+}
+
+/// The results of a simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    pub bots: Vec<String>,
+    pub rounds: Vec<RoundResult>,
+}
+
+impl Simulation {
+    /// The share of rounds each bot won, with a tied round shared between the winners.
+    pub fn win_rates(&self) -> Vec<f32> {
+        let mut wins = vec![0.; self.bots.len()];
+        for round in &self.rounds {
+            let best = round.scores.iter().copied().max().unwrap_or(0);
+            let winners = round.scores.iter().filter(|&&score| score == best).count();
+            for (wins, &score) in wins.iter_mut().zip(&round.scores) {
+                if score == best {
+                    *wins += 1. / winners as f32;
+                }
+            }
+        }
+        wins.into_iter().map(|wins| wins / self.rounds.len().max(1) as f32).collect()
+    }
+
+    /// The average score of each bot.
+    pub fn average_scores(&self) -> Vec<f32> {
+        (0..self.bots.len())
+            .map(|bot| self.rounds.iter().map(|round| round.scores[bot] as f32).sum::<f32>() / self.rounds.len().max(1) as f32)
+            .collect()
+    }
+
+    /// Writes a table of the scores in each round, followed by each bot's win rate and average
+    /// score.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "Round  Board           ")?;
+        for name in &self.bots {
+            write!(out, " {:>8}", name)?;
+        }
+        writeln!(out)?;
+        for (index, round) in self.rounds.iter().enumerate() {
+            write!(out, "{:>5}  {:<16}", index + 1, round.board)?;
+            for score in &round.scores {
+                write!(out, " {:>8}", score)?;
+            }
+            writeln!(out)?;
+        }
+
+        write!(out, "Win rate               ")?;
+        for rate in self.win_rates() {
+            write!(out, " {:>7.1}%", 100. * rate)?;
+        }
+        writeln!(out)?;
+        write!(out, "Average score          ")?;
+        for average in self.average_scores() {
+            write!(out, " {:>8.1}", average)?;
+        }
+        writeln!(out)
+    }
+}
+
+/// Plays the bots against each other on rounds random boards of round_time seconds each, drawn
+/// from the dictionary's alphabet. Round n uses a board seeded by seed + n, so the same seed
+/// gives the same boards, whichever bots play.
+pub fn simulate(dictionary: &Arc<Dictionary>, bots: &[Bot], rounds: usize, seed: u64, round_time: f32,
+                model: &TimeModel) -> Simulation {
+    let results = (0..rounds as u64)
+        .map(|round| {
+            let mut rng = Rng::new(seed.wrapping_add(round));
+            let (letters, word_int_mults, letter_mults) =
+                random_board(&dictionary.alphabet, &MultiplierCounts::default(), &mut rng);
+            let mut board = Board::new(letters, word_int_mults, letter_mults, Arc::clone(dictionary));
+            board.solve();
+            board.dedup_entries();
+
+            let scores = bots.iter()
+                .map(|bot| bot.play(&board.word_info, model, round_time, &mut rng).iter()
+                    .map(|(_, score)| *score as u32)
+                    .sum())
+                .collect();
+            RoundResult {
+                board: board.board.iter().map(|&code| board.alphabet().symbol(code)).collect(),
+                scores,
+            }
+        })
+        .collect();
+
+    Simulation { bots: bots.iter().map(|bot| bot.name.clone()).collect(), rounds: results }
+}
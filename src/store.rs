@@ -0,0 +1,236 @@
+//! The word stores a dictionary can be kept in. The search walks a store one letter at a time
+//! through WordStore::step, so each store can keep whatever state makes the next step cheap.
+use fnv::FnvHashSet;
+use crate::{PREFIX_LOWER_BOUND, PREFIX_UPPER_BOUND};
+use crate::dictionary::WordSet;
+use crate::mapped::MappedWords;
+
+/// A set of words, packed into u64s with bits_per_letter bits per letter, which can be walked
+/// one letter at a time.
+pub trait WordStore {
+    /// How far a walk through the store has got.
+    type State: Copy;
+
+    fn bits_per_letter(&self) -> u32;
+
+    /// The state before any letter has been walked.
+    fn start(&self) -> Self::State;
+
+    /// Walks one letter on from state, returning None if no word starts with the letters
+    /// walked so far. A store may return a state for some prefixes which start no word, in
+    /// which case the search carries on until it runs out of letters.
+    fn step(&self, state: Self::State, code: u8) -> Option<Self::State>;
+
+    /// Whether the letters walked to reach state make up a word.
+    fn ends_word(&self, state: Self::State) -> bool;
+
+    /// Walks the letters of a packed word from the start.
+    fn walk(&self, word: u64) -> Option<Self::State> {
+        let bits = self.bits_per_letter();
+        let mask = (1 << bits) - 1;
+        let len = (64 - word.leading_zeros()).div_ceil(bits);
+        let mut state = self.start();
+        for index in (0..len).rev() {
+            state = self.step(state, ((word >> (index * bits)) & mask) as u8)?;
+        }
+        Some(state)
+    }
+
+    fn is_word(&self, word: u64) -> bool {
+        self.walk(word).is_some_and(|state| self.ends_word(state))
+    }
+
+    /// Whether some word starts with the packed word, which may be a word itself.
+    fn is_prefix(&self, word: u64) -> bool {
+        self.walk(word).is_some()
+    }
+}
+
+/// Returns the number of letters in a packed word.
+pub(crate) fn word_len(word: u64, bits: u32) -> u8 {
+    (64 - word.leading_zeros()).div_ceil(bits) as u8
+}
+
+/// Adds the prefixes of length PREFIX_LOWER_BOUND to PREFIX_UPPER_BOUND of word to prefixes.
+pub(crate) fn insert_prefixes(prefixes: &mut WordSet, word: u64, bits: u32) {
+    let len = word_len(word, bits);
+    for prefix_len in PREFIX_LOWER_BOUND..=PREFIX_UPPER_BOUND.min(len) {
+        prefixes.insert(word >> ((len - prefix_len) as u32 * bits));
+    }
+}
+
+/// A table of packed words which can answer whether it holds a word.
+pub trait PackedWords {
+    fn contains(&self, word: &u64) -> bool;
+
+    fn packed_words(&self) -> Vec<u64>;
+}
+
+impl PackedWords for WordSet {
+    fn contains(&self, word: &u64) -> bool {
+        WordSet::contains(self, word)
+    }
+
+    fn packed_words(&self) -> Vec<u64> {
+        self.iter().copied().collect()
+    }
+}
+
+impl PackedWords for MappedWords {
+    fn contains(&self, word: &u64) -> bool {
+        MappedWords::contains(self, word)
+    }
+
+    fn packed_words(&self) -> Vec<u64> {
+        self.iter().collect()
+    }
+}
+
+/// A store holding a table of packed words and a table of their prefixes of length
+/// PREFIX_LOWER_BOUND to PREFIX_UPPER_BOUND. Its state is the packed word walked so far and its
+/// length, and longer prefixes are not checked.
+pub struct PackedStore<T> {
+    pub words: T,
+    pub prefixes: T,
+    bits: u32,
+}
+
+/// The words and prefixes in FNV hash sets.
+pub type HashStore = PackedStore<WordSet>;
+
+/// The words and prefixes in memory mapped sorted arrays.
+pub type SortedStore = PackedStore<MappedWords>;
+
+impl<T> PackedStore<T> {
+    pub fn new(words: T, prefixes: T, bits: u32) -> PackedStore<T> {
+        PackedStore { words, prefixes, bits }
+    }
+}
+
+impl HashStore {
+    /// Creates a store from a set of words, generating their prefixes.
+    pub fn from_words(words: WordSet, bits: u32) -> HashStore {
+        let mut prefixes = FnvHashSet::default();
+        for &word in &words {
+            insert_prefixes(&mut prefixes, word, bits);
+        }
+        PackedStore { words, prefixes, bits }
+    }
+}
+
+impl<T: PackedWords> WordStore for PackedStore<T> {
+    //     word, word_len
+    type State = (u64, u8);
+
+    fn bits_per_letter(&self) -> u32 {
+        self.bits
+    }
+
+    fn start(&self) -> (u64, u8) {
+        (0, 0)
+    }
+
+    fn step(&self, (word, len): (u64, u8), code: u8) -> Option<(u64, u8)> {
+        let next = ((word << self.bits) | code as u64, len + 1);
+        if (PREFIX_LOWER_BOUND..=PREFIX_UPPER_BOUND).contains(&next.1) && !self.prefixes.contains(&next.0) {
+            return None;
+        }
+        Some(next)
+    }
+
+    fn ends_word(&self, (word, _): (u64, u8)) -> bool {
+        self.words.contains(&word)
+    }
+
+    fn is_word(&self, word: u64) -> bool {
+        self.words.contains(&word)
+    }
+}
+
+/// A prefix tree, whose state is the node reached so far.
+pub struct Trie {
+    bits: u32,
+    // The children of node i are edges[first_edge[i]..first_edge[i + 1]], as (code, child) pairs.
+    first_edge: Vec<u32>,
+    edges: Vec<(u8, u32)>,
+    is_word: Vec<bool>,
+}
+
+impl Trie {
+    /// Builds a trie holding the packed words.
+    pub fn from_words<I: IntoIterator<Item = u64>>(words: I, bits: u32) -> Trie {
+        let mut children: Vec<Vec<(u8, u32)>> = vec![Vec::new()];
+        let mut is_word = vec![false];
+        let mask = (1 << bits) - 1;
+
+        for word in words {
+            let mut node = 0;
+            for index in (0..word_len(word, bits) as u32).rev() {
+                let code = ((word >> (index * bits)) & mask) as u8;
+                node = match children[node].iter().find(|&&(child_code, _)| child_code == code) {
+                    Some(&(_, child)) => child as usize,
+                    None => {
+                        let child = children.len();
+                        children[node].push((code, child as u32));
+                        children.push(Vec::new());
+                        is_word.push(false);
+                        child
+                    }
+                };
+            }
+            is_word[node] = true;
+        }
+
+        let mut first_edge = Vec::with_capacity(children.len() + 1);
+        let mut edges = Vec::with_capacity(children.len() - 1);
+        for node_children in children {
+            first_edge.push(edges.len() as u32);
+            edges.extend(node_children);
+        }
+        first_edge.push(edges.len() as u32);
+
+        Trie { bits, first_edge, edges, is_word }
+    }
+
+    /// The number of nodes in the trie, including the root.
+    pub fn node_count(&self) -> usize {
+        self.is_word.len()
+    }
+
+    /// The packed words in the trie.
+    pub fn words(&self) -> Vec<u64> {
+        let mut words = Vec::new();
+        let mut stack = vec![(0u32, 0u64)];
+        while let Some((node, word)) = stack.pop() {
+            if self.is_word[node as usize] {
+                words.push(word);
+            }
+            let edges = &self.edges[self.first_edge[node as usize] as usize..self.first_edge[node as usize + 1] as usize];
+            for &(code, child) in edges {
+                stack.push((child, (word << self.bits) | code as u64));
+            }
+        }
+        words
+    }
+}
+
+impl WordStore for Trie {
+    type State = u32;
+
+    fn bits_per_letter(&self) -> u32 {
+        self.bits
+    }
+
+    fn start(&self) -> u32 {
+        0
+    }
+
+    fn step(&self, node: u32, code: u8) -> Option<u32> {
+        let edges = &self.edges[self.first_edge[node as usize] as usize..self.first_edge[node as usize + 1] as usize];
+        edges.iter().find(|&&(child_code, _)| child_code == code).map(|&(_, child)| child)
+    }
+
+    fn ends_word(&self, node: u32) -> bool {
+        self.is_word[node as usize]
+    }
+}
@@ -0,0 +1,42 @@
+//! The ways of searching a board for its words. They all find the same words, with the same
+//! scores and paths, so one can be picked at run time and each checked against the others.
+use crate::Board;
+use crate::dictionary::Dictionary;
+use crate::general::StringSearch;
+use crate::recursive::RecursiveSearch;
+
+/// The names of the strategies, as given to strategy_by_name.
+pub const STRATEGY_NAMES: [&str; 3] = ["stack", "recursive", "string"];
+
+/// A way of finding every word in a board.
+pub trait SearchStrategy {
+    fn name(&self) -> &'static str;
+
+    /// Finds every word in the board, replacing any previously found words. The board must use
+    /// the dictionary the strategy was made for.
+    fn solve(&self, board: &mut Board);
+}
+
+/// The depth first search of Board::solve, which keeps its own stack of packed words and
+/// paths.
+pub struct StackSearch;
+
+impl SearchStrategy for StackSearch {
+    fn name(&self) -> &'static str {
+        "stack"
+    }
+
+    fn solve(&self, board: &mut Board) {
+        board.solve();
+    }
+}
+
+/// Returns the strategy named name (one of STRATEGY_NAMES) for boards using dictionary.
+pub fn strategy_by_name(name: &str, dictionary: &Dictionary) -> Option<Box<dyn SearchStrategy>> {
+    match name {
+        "stack" => Some(Box::new(StackSearch)),
+        "recursive" => Some(Box::new(RecursiveSearch)),
+        "string" => Some(Box::new(StringSearch::new(dictionary))),
+        _ => None,
+    }
+}
@@ -0,0 +1,151 @@
+//! A solving mode which hands each word to a sink as soon as it is found, and which can be
+//! stopped early by a deadline or a cancellation flag.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+use crate::{Board, BOARD_SIZE, MIN_WORD_LEN, StackEntry, gen_graph, word_score};
+use crate::store::WordStore;
+
+// Checking the clock costs about as much as a few iterations of the search, so the stopping
+// conditions are only checked once every this many iterations.
+const CHECK_INTERVAL: u32 = 256;
+
+/// Receives each word as soon as the search finds it.
+pub trait WordSink {
+    fn push(&mut self, word: String, score: u16, path: u64);
+}
+
+impl<F: FnMut(String, u16, u64)> WordSink for F {
+    fn push(&mut self, word: String, score: u16, path: u64) {
+        self(word, score, path)
+    }
+}
+
+impl WordSink for Sender<(String, u16, u64)> {
+    /// Words sent after the receiver has been dropped are discarded.
+    fn push(&mut self, word: String, score: u16, path: u64) {
+        let _ = self.send((word, score, path));
+    }
+}
+
+impl WordSink for Vec<(String, u16, u64)> {
+    fn push(&mut self, word: String, score: u16, path: u64) {
+        Vec::push(self, (word, score, path));
+    }
+}
+
+/// Describes whether a search explored the whole board, or why it stopped early.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveStatus {
+    Complete,
+    TimedOut,
+    Cancelled,
+}
+
+impl SolveStatus {
+    pub fn is_complete(self) -> bool {
+        self == SolveStatus::Complete
+    }
+}
+
+/// The conditions under which a search stops before exploring the whole board.
+#[derive(Clone, Copy, Default)]
+pub struct SearchLimits<'a> {
+    pub deadline: Option<Instant>,
+    pub cancel: Option<&'a AtomicBool>,
+}
+
+impl<'a> SearchLimits<'a> {
+    /// Limits which stop the search once time_budget has passed from now,
+    /// or once cancel is set, whichever comes first.
+    pub fn new(time_budget: Option<Duration>, cancel: Option<&'a AtomicBool>) -> SearchLimits<'a> {
+        SearchLimits {
+            deadline: time_budget.map(|budget| Instant::now() + budget),
+            cancel,
+        }
+    }
+
+    /// Returns the reason to stop the search, if there is one.
+    fn check(&self) -> Option<SolveStatus> {
+        if self.cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            Some(SolveStatus::Cancelled)
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(SolveStatus::TimedOut)
+        } else {
+            None
+        }
+    }
+}
+
+/// A non recursive depth first search which passes every word of store to sink as it is found, and
+/// stops as soon as limits says so. Returns whether the search was completed.
+pub fn streaming_dfs<W: WordStore, S: WordSink>(board: &Board, store: &W, graph: &[Vec<u8>],
+                                                limits: SearchLimits, sink: &mut S) -> SolveStatus {
+    let bits = board.alphabet().bits_per_letter();
+    let max_word_len = board.alphabet().max_word_len();
+    let mut stack: Vec<StackEntry<W::State>> = Vec::with_capacity(120);
+    for i in 0..BOARD_SIZE * BOARD_SIZE {
+        if let Some(state) = store.step(store.start(), board.board[i]) {
+            stack.push(((0b10000 | i) as u64,
+                        board.board[i] as u64,
+                        state,
+                        board.points[i] as u16,
+                        board.word_int_mults[i] as u16,
+                        1, 0));
+        }
+    }
+
+    let mut until_check = 0;
+    while let Some((path, word, state, word_pts, word_mult, mut word_len, mut visited)) = stack.pop() {
+        if until_check == 0 {
+            if let Some(status) = limits.check() {
+                return status;
+            }
+            until_check = CHECK_INTERVAL;
+        }
+        until_check -= 1;
+
+        if (MIN_WORD_LEN..=max_word_len).contains(&word_len) && store.ends_word(state) {
+            let score = word_score(word_pts, word_mult, word_len);
+            sink.push(board.alphabet().decode(word), score, path);
+        }
+
+        let vert = path & 0xF;
+        visited |= 1 << vert;
+
+        if word_len == max_word_len {
+            continue;
+        }
+
+        word_len += 1;
+
+        for &vertex in &graph[vert as usize] {
+            if ((visited >> vertex) & 1) == 0 {
+                let temp_word = (word << bits) | (board.board[vertex as usize] as u64);
+
+                let temp_state = match store.step(state, board.board[vertex as usize]) {
+                    Some(temp_state) => temp_state,
+                    None => continue,
+                };
+
+                stack.push(((path << 5) | 0b10000 | (vertex as u64), temp_word, temp_state,
+                            word_pts + board.points[vertex as usize] as u16,
+                            word_mult.saturating_mul(board.word_int_mults[vertex as usize] as u16), word_len,
+                            visited | (1 << vertex)));
+            }
+        }
+    }
+
+    SolveStatus::Complete
+}
+
+impl Board {
+    /// Finds the words in the board, passing each one to sink as soon as it is found rather
+    /// than storing it in word_info. The search stops early once time_budget has passed or
+    /// cancel is set, which is reflected in the returned status.
+    pub fn solve_streaming<S: WordSink>(&self, time_budget: Option<Duration>,
+                                        cancel: Option<&AtomicBool>, sink: &mut S) -> SolveStatus {
+        let limits = SearchLimits::new(time_budget, cancel);
+        with_store!(self.dictionary, |store| streaming_dfs(self, store, &gen_graph(), limits, sink))
+    }
+}
@@ -0,0 +1,126 @@
+//! Draws found words as vector images: the board's tiles and multipliers, with the swipe of
+//! each word drawn over them. The images can be rasterized to PNG when the crate is built with
+//! the png-export feature.
+use std::fmt::Write;
+use crate::{Board, BOARD_SIZE, path_to_vec};
+
+const CELL: u32 = 80;
+const GAP: u32 = 8;
+const MARGIN: u32 = 20;
+const HEADER: u32 = 40;
+const BOARD_WIDTH: u32 = BOARD_SIZE as u32 * CELL + (BOARD_SIZE as u32 - 1) * GAP;
+const PANEL_WIDTH: u32 = BOARD_WIDTH + 2 * MARGIN;
+const PANEL_HEIGHT: u32 = HEADER + BOARD_WIDTH + 2 * MARGIN;
+// The number of panels in each row of the image.
+const COLUMNS: usize = 3;
+
+/// Returns the label and colour of the badge for the multiplier on the tile at vertex, if the
+/// tile has one.
+fn badge(board: &Board, vertex: usize) -> Option<(&'static str, &'static str)> {
+    match (board.letter_mults[vertex], board.word_int_mults[vertex]) {
+        (_, 3) => Some(("TW", "#d9534f")),
+        (_, 2) => Some(("DW", "#e58bb5")),
+        (3, _) => Some(("TL", "#3f6fd1")),
+        (2, _) => Some(("DL", "#7fb8e6")),
+        _ => None,
+    }
+}
+
+/// Escapes the characters of text which have a meaning in XML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the top left corner of the cell in row x and column y, relative to the board.
+fn cell_corner((x, y): (u8, u8)) -> (u32, u32) {
+    (y as u32 * (CELL + GAP), x as u32 * (CELL + GAP))
+}
+
+/// Draws one word on the board, with its top left corner at (left, top).
+fn write_panel(svg: &mut String, board: &Board, (word, score, path): &(String, u16, u64), left: u32, top: u32) {
+    let alphabet = board.alphabet();
+    let (board_left, board_top) = (left + MARGIN, top + MARGIN + HEADER);
+    let _ = writeln!(svg, r##"<text x="{}" y="{}" font-size="28" font-weight="bold" fill="#333">{} ({} points)</text>"##,
+                     board_left, top + MARGIN + 28, escape(word), score);
+
+    let corners: Vec<(u32, u32)> = (0..BOARD_SIZE * BOARD_SIZE)
+        .map(|vertex| cell_corner(((vertex / BOARD_SIZE) as u8, (vertex % BOARD_SIZE) as u8)))
+        .map(|(x, y)| (board_left + x, board_top + y))
+        .collect();
+    for &(x, y) in &corners {
+        let _ = writeln!(svg, r##"<rect x="{}" y="{}" width="{}" height="{}" rx="10" fill="#f4e7c5" stroke="#b89b5e" stroke-width="2"/>"##,
+                         x, y, CELL, CELL);
+    }
+
+    // The swipe goes under the letters, so that they stay readable.
+    let centres: Vec<(u32, u32)> = path_to_vec(*path).into_iter()
+        .map(cell_corner)
+        .map(|(x, y)| (board_left + x + CELL / 2, board_top + y + CELL / 2))
+        .collect();
+    let points: Vec<String> = centres.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+    let _ = writeln!(svg, r##"<polyline points="{}" fill="none" stroke="#f08a24" stroke-opacity="0.6" stroke-width="12" stroke-linecap="round" stroke-linejoin="round"/>"##,
+                     points.join(" "));
+    if let (Some(&(x, y)), Some(&(end_x, end_y))) = (centres.first(), centres.last()) {
+        let _ = writeln!(svg, r##"<circle cx="{}" cy="{}" r="30" fill="none" stroke="#3a9d5d" stroke-width="5"/>"##, x, y);
+        let _ = writeln!(svg, r##"<circle cx="{}" cy="{}" r="10" fill="#f08a24"/>"##, end_x, end_y);
+    }
+
+    for (vertex, &(x, y)) in corners.iter().enumerate() {
+        let code = board.board[vertex];
+        let _ = writeln!(svg, r##"<text x="{}" y="{}" font-size="40" font-weight="bold" text-anchor="middle" fill="#222">{}</text>"##,
+                         x + CELL / 2, y + CELL / 2 + 14, escape(&alphabet.symbol(code).to_string()));
+        let _ = writeln!(svg, r##"<text x="{}" y="{}" font-size="14" text-anchor="end" fill="#555">{}</text>"##,
+                         x + CELL - 8, y + CELL - 8, alphabet.value(code));
+        if let Some((label, colour)) = badge(board, vertex) {
+            let _ = writeln!(svg, r##"<rect x="{}" y="{}" width="30" height="18" rx="4" fill="{}"/>"##, x + 4, y + 4, colour);
+            let _ = writeln!(svg, r##"<text x="{}" y="{}" font-size="12" font-weight="bold" text-anchor="middle" fill="#fff">{}</text>"##,
+                             x + 19, y + 17, label);
+        }
+    }
+}
+
+/// Draws each of the words on its own copy of the board, with the swipe as a line starting
+/// from a green ring, laid out in rows of three.
+pub fn render_svg(board: &Board, words: &[(String, u16, u64)]) -> String {
+    let columns = words.len().clamp(1, COLUMNS) as u32;
+    let rows = words.len().div_ceil(COLUMNS).max(1) as u32;
+    let (width, height) = (columns * PANEL_WIDTH, rows * PANEL_HEIGHT);
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" font-family="DejaVu Sans, Helvetica, Arial, sans-serif">"#,
+                     width, height);
+    let _ = writeln!(svg, r##"<rect width="{}" height="{}" fill="#fffaf0"/>"##, width, height);
+    for (index, entry) in words.iter().enumerate() {
+        let (column, row) = ((index % COLUMNS) as u32, (index / COLUMNS) as u32);
+        write_panel(&mut svg, board, entry, column * PANEL_WIDTH, row * PANEL_HEIGHT);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Rasterizes an image drawn by render_svg to a PNG file, using the system's fonts for the
+/// letters.
+#[cfg(feature = "png-export")]
+pub fn svg_to_png(svg: &str) -> Result<Vec<u8>, String> {
+    use resvg::{tiny_skia, usvg};
+
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|err| format!("The image is not valid SVG: {}", err))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or("The image has no area.".to_string())?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|err| format!("Encoding the PNG failed: {}", err))
+}
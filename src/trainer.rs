@@ -0,0 +1,304 @@
+//! Practice rounds: the player types the words they find in a board against the clock, and is
+//! told how close they came to the best possible score. Each round is added to a history file,
+//! so that progress can be followed from one round to the next.
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use crate::Board;
+
+/// The verdict on a word typed by the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Guess {
+    /// The word is in the board, and scores this many points.
+    Scored(u16),
+    /// The word was already found in this round.
+    Repeated,
+    /// The word is in the dictionary, but can not be swiped on the board.
+    NotInBoard,
+    /// The word is not in the dictionary.
+    NotAWord,
+}
+
+/// A round in progress on a solved board.
+pub struct Round<'a> {
+    board: &'a Board,
+    // The best score of each distinct word in the board.
+    scores: HashMap<&'a str, u16>,
+    found: Vec<(String, u16)>,
+}
+
+impl<'a> Round<'a> {
+    /// Starts a round on a board whose words have already been found.
+    pub fn new(board: &'a Board) -> Round<'a> {
+        let mut scores = HashMap::with_capacity(board.word_info.len());
+        for (word, score, _) in &board.word_info {
+            let best = scores.entry(word.as_str()).or_insert(*score);
+            *best = (*best).max(*score);
+        }
+        Round { board, scores, found: Vec::new() }
+    }
+
+    /// Checks a typed word against the words of the board, and scores it if it is new.
+    pub fn guess(&mut self, word: &str) -> Guess {
+        let word = word.trim().to_uppercase();
+        match self.scores.get(word.as_str()) {
+            Some(_) if self.is_found(&word) => Guess::Repeated,
+            Some(&score) => {
+                self.found.push((word, score));
+                Guess::Scored(score)
+            }
+            None => match self.board.alphabet().encode(&word) {
+                Some(encoded) if self.board.dictionary.store.is_word(encoded) => Guess::NotInBoard,
+                _ => Guess::NotAWord,
+            },
+        }
+    }
+
+    /// Every distinct word of the board, with its best score.
+    pub fn words(&self) -> impl Iterator<Item = (&str, u16)> + '_ {
+        self.scores.iter().map(|(&word, &score)| (word, score))
+    }
+
+    /// Whether the word has been found in this round.
+    pub fn is_found(&self, word: &str) -> bool {
+        self.found.iter().any(|(found, _)| found == word)
+    }
+
+    /// The words found so far, in the order they were typed, with their scores.
+    pub fn found(&self) -> &[(String, u16)] {
+        &self.found
+    }
+
+    /// Ends the round, listing at most missed_words of the best words which were not found.
+    pub fn report(&self, missed_words: usize) -> Report {
+        let mut missed: Vec<(String, u16)> = self.scores.iter()
+            .filter(|(word, _)| !self.is_found(word))
+            .map(|(word, score)| (word.to_string(), *score))
+            .collect();
+        missed.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        missed.truncate(missed_words);
+
+        Report {
+            score: self.found.iter().map(|(_, score)| *score as u32).sum(),
+            best_score: self.scores.values().map(|&score| score as u32).sum(),
+            found_words: self.found.len(),
+            total_words: self.scores.len(),
+            missed,
+        }
+    }
+}
+
+/// Returns score as a percentage of best_score, counting a board without words as perfect.
+fn percentage(score: u32, best_score: u32) -> f32 {
+    if best_score == 0 { 100. } else { 100. * score as f32 / best_score as f32 }
+}
+
+/// How well a round went.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub score: u32,
+    /// The score for finding every word in the board.
+    pub best_score: u32,
+    pub found_words: usize,
+    pub total_words: usize,
+    /// The best words which were not found, best first.
+    pub missed: Vec<(String, u16)>,
+}
+
+impl Report {
+    /// The score as a percentage of the best possible score.
+    pub fn percentage(&self) -> f32 {
+        percentage(self.score, self.best_score)
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "You found {} of the {} words, scoring {} points: {:.1}% of the {} points in the board.",
+                 self.found_words, self.total_words, self.score, self.percentage(), self.best_score)?;
+        if !self.missed.is_empty() {
+            writeln!(out, "The best words you missed:")?;
+            for (word, score) in &self.missed {
+                writeln!(out, "  {:<12} {:>4}", word, score)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lines of input, read on a thread of their own so that waiting for the next line can be
+/// given up at a deadline. Lines which are not waited for stay queued, in order, for the next
+/// read, so a round which ends while the player is typing does not lose what they type next.
+pub struct TimedLines {
+    lines: Receiver<io::Result<String>>,
+}
+
+impl TimedLines {
+    /// Starts reading input. The thread reading it stops at the end of input, or at the first
+    /// line read once the TimedLines has been dropped.
+    pub fn new<R: BufRead + Send + 'static>(input: R) -> TimedLines {
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in input.lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        TimedLines { lines }
+    }
+
+    /// Returns the next line if it is read before deadline, or None at the deadline or at the
+    /// end of input.
+    fn next_before(&mut self, deadline: Instant) -> Option<io::Result<String>> {
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            match self.lines.recv_timeout(deadline - now) {
+                Ok(line) => return Some(line),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+}
+
+impl Iterator for TimedLines {
+    type Item = io::Result<String>;
+
+    /// Waits for the next line, however long it takes.
+    fn next(&mut self) -> Option<io::Result<String>> {
+        self.lines.recv().ok()
+    }
+}
+
+/// Plays a round: words are read from input, one per line, until input ends or round_time runs
+/// out, whether or not the player is typing. Each word is checked as it is typed, and words
+/// typed after the time is up are left in input. Returns the ended round.
+pub fn play_round<'a, W: Write>(board: &'a Board, round_time: Duration, input: &mut TimedLines,
+                                out: &mut W) -> io::Result<Round<'a>> {
+    let mut round = Round::new(board);
+    writeln!(out, "You have {}s. Type one word per line.", round_time.as_secs_f32())?;
+    let deadline = Instant::now() + round_time;
+    loop {
+        let line = match input.next_before(deadline) {
+            Some(line) => line?,
+            None if Instant::now() >= deadline => {
+                writeln!(out, "Time is up.")?;
+                break;
+            }
+            None => break,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match round.guess(&line) {
+            Guess::Scored(score) => writeln!(out, "+{}", score)?,
+            Guess::Repeated => writeln!(out, "You already found that word.")?,
+            Guess::NotInBoard => writeln!(out, "That word is not in the board.")?,
+            Guess::NotAWord => writeln!(out, "That is not a word.")?,
+        }
+    }
+    Ok(round)
+}
+
+/// One round in the history file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// When the round was played, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub dictionary: String,
+    /// The letters of the board, row by row.
+    pub board: String,
+    pub score: u32,
+    pub best_score: u32,
+    pub found_words: usize,
+    pub total_words: usize,
+}
+
+impl HistoryEntry {
+    /// Records a round on board which has just ended.
+    pub fn new(board: &Board, report: &Report) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs()),
+            dictionary: board.dictionary.name.clone(),
+            board: board.board.iter().map(|&code| board.alphabet().symbol(code)).collect(),
+            score: report.score,
+            best_score: report.best_score,
+            found_words: report.found_words,
+            total_words: report.total_words,
+        }
+    }
+
+    pub fn percentage(&self) -> f32 {
+        percentage(self.score, self.best_score)
+    }
+
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\t{}\t{}\t{}", self.timestamp, self.dictionary, self.board, self.score,
+                self.best_score, self.found_words, self.total_words)
+    }
+
+    fn parse(line: &str) -> Option<HistoryEntry> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 7 {
+            return None;
+        }
+        Some(HistoryEntry {
+            timestamp: fields[0].parse().ok()?,
+            dictionary: fields[1].to_string(),
+            board: fields[2].to_string(),
+            score: fields[3].parse().ok()?,
+            best_score: fields[4].parse().ok()?,
+            found_words: fields[5].parse().ok()?,
+            total_words: fields[6].parse().ok()?,
+        })
+    }
+}
+
+/// Reads the rounds in the history file at path, oldest first. A missing file has no rounds.
+/// The file is tab separated, with one round per line.
+pub fn read_history(path: &str) -> Result<Vec<HistoryEntry>, String> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("The history file {} could not be read: {}", path, err)),
+    };
+    BufReader::new(file).lines().enumerate()
+        .map(|(index, line)| {
+            let line = line.map_err(|err| format!("The history file {} could not be read: {}", path, err))?;
+            HistoryEntry::parse(&line)
+                .ok_or(format!("Line {} of the history file {} is not a round.", index + 1, path))
+        })
+        .collect()
+}
+
+/// Adds a round to the end of the history file at path, creating the file if needed.
+pub fn append_history(path: &str, entry: &HistoryEntry) -> Result<(), String> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)
+        .map_err(|err| format!("The history file {} could not be opened: {}", path, err))?;
+    writeln!(file, "{}", entry.to_line()).map_err(|err| format!("The history file {} could not be written: {}", path, err))
+}
+
+/// Writes how the last rounds went compared to all the rounds before them.
+pub fn write_progress<W: Write>(history: &[HistoryEntry], recent: usize, out: &mut W) -> io::Result<()> {
+    if history.is_empty() {
+        return Ok(());
+    }
+    let average = |rounds: &[HistoryEntry]| rounds.iter().map(HistoryEntry::percentage).sum::<f32>() / rounds.len() as f32;
+    let split = history.len().saturating_sub(recent);
+    let (earlier, latest) = history.split_at(split);
+    let best = history.iter().map(|entry| entry.score).max().unwrap_or(0);
+
+    match latest.len() {
+        1 => write!(out, "In your last round you scored {:.1}%", average(latest))?,
+        rounds => write!(out, "Over your last {} rounds you scored {:.1}% on average", rounds, average(latest))?,
+    }
+    if !earlier.is_empty() {
+        write!(out, ", against {:.1}% before that", average(earlier))?;
+    }
+    writeln!(out, ". Your best round scored {} points.", best)
+}
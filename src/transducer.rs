@@ -0,0 +1,94 @@
+//! A dictionary backend which stores the words in a finite state transducer. Every prefix of a
+//! word is a path from the root of the transducer, so a single table answers both word and
+//! prefix queries, in a fraction of the space of the packed word and prefix tables.
+use std::fs::File;
+use std::io::{self, BufWriter};
+use fst::raw::CompiledAddr;
+use fst::{Set, SetBuilder, Streamer};
+use memmap2::Mmap;
+use crate::store::{WordStore, word_len};
+
+/// A memory mapped set of words, each stored as the codes of its letters, one byte per letter.
+/// Its state is the address of the node reached so far.
+pub struct FstWords {
+    set: Set<Mmap>,
+    bits: u32,
+}
+
+/// Splits a packed word into its letter codes, starting from the first letter.
+fn unpack(word: u64, bits: u32) -> Vec<u8> {
+    let mask = (1 << bits) - 1;
+    (0..word_len(word, bits) as u32).rev()
+        .map(|index| ((word >> (index * bits)) & mask) as u8)
+        .collect()
+}
+
+impl FstWords {
+    /// Maps the transducer at file_path, for words packed with bits bits per letter.
+    pub fn open(file_path: &str, bits: u32) -> io::Result<FstWords> {
+        let file = File::open(file_path)?;
+        // As with the sorted tables, the transducer must not be modified while it is mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        let set = Set::new(map).map_err(|err| io::Error::new(io::ErrorKind::InvalidData,
+                                                              format!("{} is not a transducer: {}", file_path, err)))?;
+        Ok(FstWords { set, bits })
+    }
+
+    /// Writes the packed words to a transducer at file_path, in the format read by open.
+    pub fn write<I: IntoIterator<Item = u64>>(file_path: &str, words: I, bits: u32) -> io::Result<()> {
+        let mut keys: Vec<Vec<u8>> = words.into_iter().map(|word| unpack(word, bits)).collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let writer = BufWriter::new(File::create(file_path)?);
+        let mut builder = SetBuilder::new(writer).map_err(io::Error::other)?;
+        builder.extend_iter(keys).map_err(io::Error::other)?;
+        builder.finish().map_err(io::Error::other)
+    }
+
+    /// The number of words in the set.
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// The packed words in the set, in order of their letters.
+    pub fn words(&self) -> Vec<u64> {
+        let mut words = Vec::with_capacity(self.len());
+        let mut stream = self.set.stream();
+        while let Some(codes) = stream.next() {
+            words.push(codes.iter().fold(0, |word, &code| (word << self.bits) | code as u64));
+        }
+        words
+    }
+
+    /// The size of the transducer in bytes.
+    pub fn size(&self) -> usize {
+        self.set.as_fst().size()
+    }
+}
+
+impl WordStore for FstWords {
+    type State = CompiledAddr;
+
+    fn bits_per_letter(&self) -> u32 {
+        self.bits
+    }
+
+    fn start(&self) -> CompiledAddr {
+        self.set.as_fst().root().addr()
+    }
+
+    fn step(&self, addr: CompiledAddr, code: u8) -> Option<CompiledAddr> {
+        let node = self.set.as_fst().node(addr);
+        let index = node.find_input(code)?;
+        Some(node.transition_addr(index))
+    }
+
+    fn ends_word(&self, addr: CompiledAddr) -> bool {
+        self.set.as_fst().node(addr).is_final()
+    }
+}
@@ -0,0 +1,92 @@
+//! A full screen view of a solved board: the found words in a list which can be scrolled, and
+//! the board with the swipe of the selected word drawn on it.
+use std::io::{self, Write};
+use crossterm::{cursor, queue, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crate::Board;
+use crate::pretty::{PrettyStyle, render_path};
+
+// The column the word list starts in, right of the board.
+const LIST_COLUMN: u16 = 34;
+
+/// Returns the index of the first word shown, so that the selected word stays in view.
+fn scroll_offset(selected: usize, offset: usize, height: usize) -> usize {
+    if selected < offset {
+        selected
+    } else if selected >= offset + height {
+        selected + 1 - height
+    } else {
+        offset
+    }
+}
+
+fn draw<W: Write>(out: &mut W, board: &Board, words: &[(String, u16, u64)], selected: usize,
+                  offset: usize, height: usize) -> io::Result<()> {
+    queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0),
+           Print(format!("{} words, from the {} dictionary", words.len(), board.dictionary.name)))?;
+
+    if let Some((word, score, path)) = words.get(selected) {
+        queue!(out, cursor::MoveTo(0, 2), Print(format!("{} ({} points)", word, score)))?;
+        for (row, line) in render_path(board, *path, PrettyStyle::Colour).lines().enumerate() {
+            queue!(out, cursor::MoveTo(0, 3 + row as u16), Print(line))?;
+        }
+        if let Some(definition) = board.word_metadata(word).and_then(|info| info.definition.as_deref()) {
+            queue!(out, cursor::MoveTo(0, 17), Print(definition))?;
+        }
+    }
+
+    for (row, (index, (word, score, _))) in words.iter().enumerate().skip(offset).take(height).enumerate() {
+        queue!(out, cursor::MoveTo(LIST_COLUMN, 2 + row as u16))?;
+        if index == selected {
+            queue!(out, SetAttribute(Attribute::Reverse))?;
+        }
+        queue!(out, Print(format!("{:>3}. {:<12} {:>4}", index + 1, word, score)), SetAttribute(Attribute::Reset))?;
+    }
+
+    let (_, rows) = terminal::size()?;
+    queue!(out, cursor::MoveTo(0, rows.saturating_sub(1)),
+           Print("Up/Down or j/k: select   PgUp/PgDn: scroll   Home/End   q: quit"))?;
+    out.flush()
+}
+
+/// Shows the words of board until the player quits.
+fn browse<W: Write>(out: &mut W, board: &Board, words: &[(String, u16, u64)]) -> io::Result<()> {
+    let (mut selected, mut offset) = (0, 0);
+    loop {
+        let (_, rows) = terminal::size()?;
+        let height = (rows as usize).saturating_sub(4).max(1);
+        offset = scroll_offset(selected, offset, height);
+        draw(out, board, words, selected, offset, height)?;
+
+        let last = words.len().saturating_sub(1);
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            selected = match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => (selected + 1).min(last),
+                KeyCode::PageUp => selected.saturating_sub(height),
+                KeyCode::PageDown => (selected + height).min(last),
+                KeyCode::Home => 0,
+                KeyCode::End => last,
+                _ => selected,
+            };
+        }
+    }
+}
+
+/// Takes over the terminal to browse the found words of board, in their current order,
+/// restoring the terminal when the player quits.
+pub fn run_tui(board: &Board) -> io::Result<()> {
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = browse(&mut out, board, &board.word_info);
+    queue!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    out.flush()?;
+    terminal::disable_raw_mode()?;
+    result
+}
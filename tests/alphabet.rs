@@ -0,0 +1,116 @@
+use std::fs;
+use ruzzle_solver::get_points;
+use ruzzle_solver::alphabet::Alphabet;
+
+/// Writes contents to a file of its own in the temporary directory, and returns its path.
+fn write_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("ruzzle_solver_{}_{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn letters_are_folded_to_upper_case() {
+    let german = Alphabet::german();
+    assert_eq!(german.code('a'), german.code('A'));
+    assert_eq!(german.code('ä'), german.code('Ä'));
+    assert_ne!(german.code('Ä'), german.code('A'));
+    assert_eq!(german.encode("Hände"), german.encode("HÄNDE"));
+    // ß has no single upper case letter, and is not a German letter of the game.
+    assert_eq!(german.code('ß'), None);
+}
+
+#[test]
+fn decomposed_and_foreign_accents_are_normalised() {
+    let swedish = Alphabet::swedish();
+    // Å written as A and a combining ring above.
+    let decomposed = swedish.encode("A\u{30A}L").unwrap();
+    assert_eq!(decomposed, swedish.encode("\u{C5}L").unwrap());
+    assert_eq!(swedish.decode(decomposed), "ÅL");
+
+    // Accented letters outside of the alphabet are read as their unaccented letter.
+    let english = Alphabet::english();
+    assert_eq!(english.encode("café"), english.encode("CAFE"));
+    assert_eq!(english.encode("cafe\u{301}"), english.encode("CAFE"));
+    assert_eq!(english.encode("ÅL"), english.encode("AL"));
+    assert_eq!(english.encode("ÞORN"), None);
+}
+
+#[test]
+fn each_language_has_its_own_letters_and_values() {
+    let value = |alphabet: &Alphabet, letter| alphabet.value(alphabet.code(letter).unwrap());
+    assert_eq!((value(&Alphabet::english(), 'Q'), value(&Alphabet::english(), 'E')), (10, 1));
+    assert_eq!((value(&Alphabet::french(), 'K'), value(&Alphabet::french(), 'W')), (10, 10));
+    assert_eq!((value(&Alphabet::german(), 'Ä'), value(&Alphabet::german(), 'Ö')), (6, 8));
+    assert_eq!((value(&Alphabet::italian(), 'G'), value(&Alphabet::italian(), 'Z')), (8, 8));
+    assert_eq!((value(&Alphabet::spanish(), 'Ñ'), value(&Alphabet::spanish(), 'Q')), (8, 5));
+    assert_eq!((value(&Alphabet::swedish(), 'Å'), value(&Alphabet::swedish(), 'C')), (4, 8));
+
+    // Italian has no K, so a K folds to nothing.
+    assert_eq!(Alphabet::italian().code('K'), None);
+    assert_eq!(Alphabet::italian().len(), 21);
+    assert_eq!(Alphabet::german().len(), 29);
+    for name in ["en", "fr", "de", "it", "es", "sv"] {
+        let alphabet = Alphabet::by_name(name).unwrap();
+        assert_eq!(alphabet.name(), name);
+        assert_eq!((alphabet.bits_per_letter(), alphabet.max_word_len()), (5, 12));
+    }
+    assert!(Alphabet::by_name("xx").is_none());
+}
+
+#[test]
+fn alphabets_are_read_from_files() {
+    // The second letter is a decomposed lower case ä, and blank lines are skipped.
+    let path = write_file("alphabet.txt", "a 1\na\u{308} 6\n\nþ 12\n");
+    let alphabet = Alphabet::read("custom", &path).unwrap();
+    assert_eq!(alphabet.name(), "custom");
+    assert_eq!(alphabet.len(), 3);
+    assert_eq!((alphabet.symbol(1), alphabet.symbol(2), alphabet.symbol(3)), ('A', 'Ä', 'Þ'));
+    assert_eq!((alphabet.value(1), alphabet.value(2), alphabet.value(3)), (1, 6, 12));
+    assert_eq!(alphabet.decode(alphabet.encode("þä").unwrap()), "ÞÄ");
+
+    for (name, contents) in [("no_value.txt", "A\n"), ("two_letters.txt", "AB 2\n"), ("bad_value.txt", "A x\n"),
+                             ("repeated.txt", "A 1\na 2\n"), ("empty.txt", ""), ("too_valuable.txt", "A 86\n")] {
+        let path = write_file(name, contents);
+        assert!(Alphabet::read(name, &path).is_err(), "{} was read", name);
+        fs::remove_file(path).unwrap();
+    }
+    assert!(Alphabet::read("missing", "no/such/alphabet.txt").is_err());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn large_alphabets_use_six_bits_and_words_of_up_to_ten_letters() {
+    let letters: Vec<(char, u8)> = ('A'..='Z').chain('Α'..='Ν').map(|letter| (letter, 1)).collect();
+    assert_eq!(letters.len(), 39);
+    let alphabet = Alphabet::new("large", &letters).unwrap();
+    assert_eq!((alphabet.bits_per_letter(), alphabet.max_word_len()), (6, 10));
+
+    // Letters with codes above 31 need the sixth bit.
+    let word = "ΝΜΛZAΑΒΓΔΕ";
+    let encoded = alphabet.encode(word).unwrap();
+    assert_eq!(alphabet.decode(encoded), word);
+    assert_eq!(alphabet.encode("ΝΜΛZAΑΒΓΔΕΖ"), None);
+
+    let small = Alphabet::new("small", &letters[..31]).unwrap();
+    assert_eq!((small.bits_per_letter(), small.max_word_len()), (5, 12));
+    assert!(small.encode("ABCDEFGHIJKL").is_some());
+    assert!(small.encode("ABCDEFGHIJKLM").is_none());
+
+    let too_many: Vec<(char, u8)> = ('A'..='Z').chain('Α'..='Ω').chain('а'..='я').map(|letter| (letter, 1)).collect();
+    assert!(Alphabet::new("too many", &too_many[..64]).is_err());
+    assert!(Alphabet::new("empty", &[]).is_err());
+}
+
+#[test]
+fn tripled_letters_of_the_highest_value_fit_in_their_points() {
+    assert!(Alphabet::new("too valuable", &[('A', 1), ('B', 86)]).is_err());
+    let alphabet = Alphabet::new("valuable", &[('A', 1), ('B', 85)]).unwrap();
+    let mut board = [1; 16];
+    board[0] = 2;
+    let mut letter_mults = [1; 16];
+    letter_mults[0] = 3;
+    letter_mults[1] = 2;
+    let points = get_points(&board, &letter_mults, &alphabet);
+    assert_eq!(points[..3], [255, 2, 1]);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::sync::Arc;
+use ruzzle_solver::Board;
+use ruzzle_solver::dictionary::Dictionary;
+use ruzzle_solver::random::{MultiplierCounts, Rng, random_board};
+
+fn random(dictionary: &Arc<Dictionary>, seed: u64) -> Board {
+    let (letters, word_int_mults, letter_mults) =
+        random_board(&dictionary.alphabet, &MultiplierCounts::default(), &mut Rng::new(seed));
+    Board::new(letters, word_int_mults, letter_mults, Arc::clone(dictionary))
+}
+
+/// Returns the words and scores of board's found words, leaving out their paths, which may
+/// differ between searches when two paths give the same score.
+fn scores(board: &Board) -> Vec<(String, u16)> {
+    let mut scores: Vec<(String, u16)> = board.word_info.iter().map(|(word, score, _)| (word.clone(), *score)).collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    scores
+}
+
+#[test]
+fn top_k_keeps_the_best_distinct_words_of_the_full_search() {
+    let dictionary = common::shipped_dictionary();
+    for seed in 0..200 {
+        let mut board = random(&dictionary, seed);
+        board.solve();
+        board.dedup_entries();
+        let all = scores(&board);
+        assert!(all.len() > 20, "board {} has only {} words", seed, all.len());
+
+        for k in [2, 5, 20, 100, all.len() + 1] {
+            board.solve_top_k(k);
+            let top = scores(&board);
+            assert_eq!(top.len(), k.min(all.len()), "board {} keeps the wrong number of words for k = {}", seed, k);
+            // Words tied with the last kept word may be swapped for one another, but the scores
+            // must be the k best, and each kept word must have its best score.
+            let best: Vec<u16> = all.iter().take(k).map(|(_, score)| *score).collect();
+            assert_eq!(top.iter().map(|(_, score)| *score).collect::<Vec<_>>(), best, "board {} differs for k = {}", seed, k);
+            for entry in &top {
+                assert!(all.contains(entry), "board {} keeps {:?} for k = {}", seed, entry, k);
+            }
+            assert!(board.word_info.windows(2).all(|pair| pair[0].1 >= pair[1].1), "board {} is not sorted", seed);
+        }
+    }
+}
+
+#[test]
+fn min_score_keeps_every_word_of_the_full_search_scoring_enough() {
+    let dictionary = common::shipped_dictionary();
+    for seed in 0..200 {
+        let mut board = random(&dictionary, seed);
+        board.solve();
+        let all = scores(&board);
+
+        for min_score in [0, 10, 25, 50, 100, u16::MAX] {
+            board.solve_min_score(min_score);
+            let expected: Vec<(String, u16)> = all.iter().filter(|(_, score)| *score >= min_score).cloned().collect();
+            assert_eq!(scores(&board), expected, "board {} differs for a minimum of {}", seed, min_score);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use ruzzle_solver::{Board, parse_board_and_mults};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::{Dictionary, Store};
+use ruzzle_solver::store::HashStore;
+
+/// A board full of common letters, with hundreds of paths through words of TWL06.
+pub const DENSE_BOARD: [&str; 2] = ["SERA TINE LAOD REST", "-D-- --2- T--- ---3"];
+
+/// An English dictionary holding only words.
+pub fn dictionary(words: &[&str]) -> Arc<Dictionary> {
+    let alphabet = Alphabet::english();
+    let words = words.iter().map(|word| alphabet.encode(word).unwrap()).collect();
+    let store = Store::Hashed(HashStore::from_words(words, alphabet.bits_per_letter()));
+    Arc::new(Dictionary::new("test", alphabet, store))
+}
+
+/// The board given by lines, in the form of a board file, using dictionary. No words are
+/// found yet.
+pub fn board(dictionary: &Arc<Dictionary>, lines: &[&str]) -> Board {
+    let lines = lines.iter().map(|line| line.to_string()).collect();
+    let (letters, word_int_mults, letter_mults) = parse_board_and_mults(lines, &dictionary.alphabet);
+    Board::new(letters, word_int_mults, letter_mults, Arc::clone(dictionary))
+}
+
+/// The shipped TWL06 word list, read in place from its sorted tables.
+pub fn shipped_dictionary() -> Arc<Dictionary> {
+    let dictionary = Dictionary::read_mapped("twl06", "data/TWL06/words.sorted", "data/prefixes/prefixes.sorted",
+                                             Alphabet::english());
+    Arc::new(dictionary.unwrap())
+}
+
+/// DENSE_BOARD, using the shipped word list. No words are found yet.
+pub fn dense_board() -> Board {
+    board(&shipped_dictionary(), &DENSE_BOARD)
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use ruzzle_solver::Board;
+use ruzzle_solver::compare::compare;
+use ruzzle_solver::game::Match;
+
+/// A triple letter on the P, which makes PINK the best word. ZEBRA is a word, but not in the
+/// board.
+const BOARD: [&str; 2] = ["STAR HOPE LINK DUSK", "---- --T- ---- ----"];
+const WORDS: [&str; 7] = ["HOP", "HOPE", "INK", "LINK", "PINK", "STAR", "ZEBRA"];
+
+fn solved_board() -> Board {
+    let mut board = common::board(&common::dictionary(&WORDS), &BOARD);
+    board.solve();
+    board
+}
+
+#[test]
+fn splits_the_words_between_the_players() {
+    let comparison = compare(&solved_board(), ("ann", "bob"), &["pink", "Link", "link", "zebra"], &["PINK", "hope", "xyz"], 10);
+
+    assert_eq!(comparison.only_a, [("LINK".to_string(), 8)]);
+    assert_eq!(comparison.only_b, [("HOPE".to_string(), 18)]);
+    assert_eq!(comparison.both, [("PINK".to_string(), 19)]);
+    assert_eq!(comparison.missed, [("HOP".to_string(), 17), ("INK".to_string(), 7), ("STAR".to_string(), 4)]);
+    assert_eq!(comparison.invalid, (vec!["ZEBRA".to_string()], vec!["XYZ".to_string()]));
+    assert_eq!((comparison.scores, comparison.max_score), ((27, 37), 73));
+    let (share_a, share_b) = comparison.shares();
+    assert!((share_a - 100. * 27. / 73.).abs() < 1e-4);
+    assert!((share_b - 100. * 37. / 73.).abs() < 1e-4);
+}
+
+#[test]
+fn lists_the_best_words_neither_found() {
+    let comparison = compare(&solved_board(), ("ann", "bob"), &["ink"], &[] as &[&str], 1);
+    assert_eq!(comparison.missed, [("PINK".to_string(), 19)]);
+
+    let mut out = Vec::new();
+    comparison.write(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "\
+ann scored 7 points (9.6% of the 73 in the board), and bob scored 0 points (0.0%).
+Only ann found (1 word):
+  INK             7
+Only bob found (0 words):
+Both found (0 words):
+Neither found (1 word):
+  PINK           19
+");
+}
+
+#[test]
+fn compares_the_players_of_a_match() {
+    let boards = (0..3).map(|_| solved_board()).collect();
+    let mut game = Match::new(&["ann", "bob"], boards).unwrap();
+    game.guess(0, "hop");
+    game.guess(1, "hop");
+    game.guess(1, "ink");
+
+    let comparison = game.compare(0, 0, 1);
+    assert!(comparison.only_a.is_empty());
+    assert_eq!(comparison.only_b, [("INK".to_string(), 7)]);
+    assert_eq!(comparison.both, [("HOP".to_string(), 17)]);
+    assert_eq!(comparison.scores, (17, 24));
+}
@@ -0,0 +1,158 @@
+use std::fs;
+use std::path::PathBuf;
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::{Dictionary, DictionaryConfig, Layer, LayerKind, Registry, Source};
+use ruzzle_solver::mapped::MappedWords;
+
+/// Writes words, one per line, to a file in the temporary directory and returns its path.
+fn write_list(name: &str, words: &[&str]) -> String {
+    let path: PathBuf = std::env::temp_dir().join(format!("ruzzle_solver_{}_{}.txt", std::process::id(), name));
+    fs::write(&path, words.join("\n")).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn sorted(mut words: Vec<u64>) -> Vec<u64> {
+    words.sort_unstable();
+    words
+}
+
+#[test]
+fn layers_add_and_remove_words_with_their_prefixes() {
+    let english = Alphabet::english();
+    let base = Dictionary::read_word_list("base", &write_list("base", &["HUNTER", "HUNT", "TREE"]), english.clone())
+        .unwrap();
+    let allow = write_list("allow", &["ZYZZYVA", "TREE"]);
+    let deny = write_list("deny", &["HUNTER"]);
+    let dictionary = Dictionary::with_layers("merged", &base, &[
+        Layer { kind: LayerKind::Allow, path: allow.clone() },
+        Layer { kind: LayerKind::Deny, path: deny },
+    ]).unwrap();
+
+    let encode = |word: &str| english.encode(word).unwrap();
+    assert!(!dictionary.store.is_word(encode("HUNTER")));
+    assert!(dictionary.store.is_word(encode("ZYZZYVA")));
+    assert!(dictionary.store.prefixes().contains(&encode("ZYZZ")));
+    assert!(dictionary.store.prefixes().contains(&encode("HUNT")));
+    assert!(!dictionary.store.prefixes().contains(&encode("HUNTE")));
+
+    assert_eq!(dictionary.layer_of(encode("ZYZZYVA")), Some(allow.as_str()));
+    assert_eq!(dictionary.layer_of(encode("TREE")), Some("base"));
+    assert_eq!(dictionary.layer_of(encode("HUNTER")), None);
+}
+
+#[test]
+fn later_layers_override_earlier_ones() {
+    let base = Dictionary::read_word_list("base", &write_list("override_base", &["HUNT"]), Alphabet::english())
+        .unwrap();
+    let list = write_list("override", &["HUNT", "TREE"]);
+    let dictionary = Dictionary::with_layers("merged", &base, &[
+        Layer { kind: LayerKind::Deny, path: list.clone() },
+        Layer { kind: LayerKind::Allow, path: list.clone() },
+    ]).unwrap();
+
+    let hunt = base.alphabet.encode("HUNT").unwrap();
+    assert_eq!(dictionary.layer_of(hunt), Some(list.as_str()));
+    assert_eq!(dictionary.layers(), ["base".to_string(), list]);
+}
+
+#[test]
+fn registry_rejects_dictionaries_layered_on_each_other() {
+    let deny = write_list("cycle", &["HUNT"]);
+    let mut registry = Registry::default();
+    for (name, base) in [("a", "b"), ("b", "a")] {
+        registry.register(DictionaryConfig {
+            name: name.to_string(),
+            alphabet: Alphabet::english(),
+            source: Source::Layered {
+                base: base.to_string(),
+                layers: vec![Layer { kind: LayerKind::Deny, path: deny.clone() }],
+            },
+            metadata: None,
+        });
+    }
+
+    assert!(registry.get("a").is_err());
+}
+
+#[test]
+fn mapped_tables_hold_the_same_words() {
+    let english = Alphabet::english();
+    let words = write_list("mapped", &["HUNTER", "HUNT", "TREE", "ZYZZYVA", "AA"]);
+    let dictionary = Dictionary::read_word_list("text", &words, english.clone()).unwrap();
+    let (words_path, prefixes_path) = (format!("{}.words", words), format!("{}.prefixes", words));
+    dictionary.write_mapped(&words_path, &prefixes_path).unwrap();
+    let mapped = Dictionary::read_mapped("mapped", &words_path, &prefixes_path, english.clone()).unwrap();
+
+    assert_eq!(mapped.store.words().len(), 5);
+    assert_eq!(sorted(mapped.store.words()), sorted(dictionary.store.words()));
+    assert_eq!(mapped.store.prefixes(), dictionary.store.prefixes());
+    for word in ["HUNTER", "AA", "ZYZZYVA"] {
+        assert!(mapped.store.is_word(english.encode(word).unwrap()));
+    }
+    for word in ["HUNTE", "A", "ZZZ", "TREES"] {
+        assert!(!mapped.store.is_word(english.encode(word).unwrap()));
+    }
+}
+
+#[test]
+fn mapped_tables_reject_other_files() {
+    assert!(MappedWords::open(&write_list("not_mapped", &["HUNTER"])).is_err());
+}
+
+#[test]
+fn transducers_answer_word_and_prefix_queries() {
+    let english = Alphabet::english();
+    let words = write_list("fst", &["HUNTER", "HUNT", "TREE", "ZYZZYVA", "AA"]);
+    let dictionary = Dictionary::read_word_list("text", &words, english.clone()).unwrap();
+    let path = format!("{}.fst", words);
+    dictionary.write_fst(&path).unwrap();
+    let fst = Dictionary::read_fst("fst", &path, english.clone()).unwrap();
+
+    assert_eq!(sorted(fst.store.words()), sorted(dictionary.store.words()));
+    assert_eq!(fst.store.prefixes(), dictionary.store.prefixes());
+    let encode = |word: &str| english.encode(word).unwrap();
+    assert!(fst.store.is_word(encode("HUNT")));
+    assert!(!fst.store.is_word(encode("HUNTE")));
+    assert!(fst.store.is_prefix(encode("HUNTE")));
+    assert!(fst.store.is_prefix(encode("ZYZZYV")));
+    assert!(!fst.store.is_prefix(encode("HUNTS")));
+}
+
+#[test]
+fn registry_reloads_dictionaries_layered_on_a_replaced_one() {
+    let word_list = |name: &str, words: &[&str]| DictionaryConfig {
+        name: name.to_string(),
+        alphabet: Alphabet::english(),
+        source: Source::WordList(write_list(&format!("replaced_{}", words.join("_")), words)),
+        metadata: None,
+    };
+    let layered = |name: &str, base: &str| DictionaryConfig {
+        name: name.to_string(),
+        alphabet: Alphabet::english(),
+        source: Source::Layered {
+            base: base.to_string(),
+            layers: vec![Layer { kind: LayerKind::Allow, path: write_list("replaced_allow", &["TREE"]) }],
+        },
+        metadata: None,
+    };
+    let mut registry = Registry::default();
+    registry.register(word_list("base", &["HUNT"]));
+    registry.register(layered("house", "base"));
+    registry.register(layered("club", "house"));
+    registry.register(word_list("other", &["RUN"]));
+
+    let hunt = Alphabet::english().encode("HUNT").unwrap();
+    let run = Alphabet::english().encode("RUN").unwrap();
+    let old_club = registry.get("club").unwrap();
+    let other = registry.get("other").unwrap();
+    assert!(old_club.store.is_word(hunt));
+
+    registry.register(word_list("base", &["RUN"]));
+    let club = registry.get("club").unwrap();
+    assert!(!club.store.is_word(hunt));
+    assert!(club.store.is_word(run));
+    assert!(registry.get("house").unwrap().store.is_word(run));
+    // Dictionaries already handed out keep their words, and unrelated ones are not reloaded.
+    assert!(old_club.store.is_word(hunt));
+    assert!(std::sync::Arc::ptr_eq(&registry.get("other").unwrap(), &other));
+}
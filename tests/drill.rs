@@ -0,0 +1,116 @@
+mod common;
+
+use std::fs;
+use std::io::Cursor;
+use ruzzle_solver::drill::{DrillItem, PlayerStats, check_player_name, run_drill};
+use ruzzle_solver::trainer::Round;
+
+/// TAMER and TIMER share their end, TIME and TIMER their start, and PLUS shares nothing. The
+/// double word on the R makes both five letter words worth 19 points.
+const BOARD: [&str; 2] = ["TAME OIDR PLUS GNAW", "---- ---2 ---- ----"];
+const WORDS: [&str; 5] = ["AIM", "PLUS", "TAMER", "TIME", "TIMER"];
+const DAY: u64 = 24 * 60 * 60;
+
+/// Plays a round in which only the given words are found.
+fn record(stats: &mut PlayerStats, player: &str, found: &[&str], now: u64) {
+    let mut board = common::board(&common::dictionary(&WORDS), &BOARD);
+    board.solve();
+    let mut round = Round::new(&board);
+    for word in found {
+        round.guess(word);
+    }
+    stats.record_round(player, &round, now);
+}
+
+#[test]
+fn rounds_record_found_and_missed_words() {
+    let mut stats = PlayerStats::new();
+    record(&mut stats, "sam", &["aim"], 100);
+    record(&mut stats, "sam", &["aim", "time"], 200);
+
+    let timer = stats.get("sam", "TIMER").unwrap();
+    assert_eq!((timer.found, timer.missed, timer.best_score, timer.due), (0, 2, 19, 200));
+    let time = stats.get("sam", "TIME").unwrap();
+    assert_eq!((time.found, time.missed), (1, 1));
+    assert_eq!(stats.get("sam", "AIM").unwrap().found, 2);
+    assert_eq!(stats.get("kim", "AIM"), None);
+
+    // TAMER and TIMER share their end, and so do their misses, while TIME and TIMER share
+    // their start.
+    let patterns = stats.missed_patterns("sam", 2);
+    assert_eq!(patterns[..4], [
+        ("*ER".to_string(), 4, 0), ("*MER".to_string(), 4, 0), ("TI*".to_string(), 3, 1), ("TIM*".to_string(), 3, 1),
+    ]);
+    assert!(patterns.iter().all(|(pattern, _, _)| pattern != "AI*"));
+    assert!(stats.missed_patterns("kim", 1).is_empty());
+}
+
+#[test]
+fn drills_follow_the_spaced_repetition_schedule() {
+    let mut stats = PlayerStats::new();
+    record(&mut stats, "sam", &[], 0);
+    record(&mut stats, "sam", &["aim"], 0);
+
+    // Words with often missed patterns come first, and their clues show the pattern.
+    let drill = stats.drill("sam", 0, 10);
+    assert_eq!(drill, [
+        DrillItem { word: "TAMER".to_string(), clue: "T?MER".to_string(), best_score: 19 },
+        DrillItem { word: "TIMER".to_string(), clue: "TIM??".to_string(), best_score: 19 },
+        DrillItem { word: "TIME".to_string(), clue: "TIM?".to_string(), best_score: 6 },
+        DrillItem { word: "PLUS".to_string(), clue: "PLU?".to_string(), best_score: 8 },
+        DrillItem { word: "AIM".to_string(), clue: "A??".to_string(), best_score: 5 },
+    ]);
+    assert_eq!(stats.drill("sam", 0, 1).len(), 1);
+
+    stats.answer("sam", "TAMER", true, 0);
+    assert_eq!(stats.get("sam", "TAMER").unwrap().due, DAY);
+    assert!(stats.drill("sam", DAY - 1, 10).iter().all(|item| item.word != "TAMER"));
+    assert_eq!(stats.drill("sam", DAY, 10)[0].word, "TAMER");
+
+    stats.answer("sam", "TAMER", true, DAY);
+    assert_eq!(stats.get("sam", "TAMER").unwrap().due, 4 * DAY);
+    stats.answer("sam", "TAMER", false, 4 * DAY);
+    assert_eq!(stats.get("sam", "TAMER").unwrap().level, 0);
+    assert_eq!(stats.get("sam", "TAMER").unwrap().due, 4 * DAY);
+}
+
+#[test]
+fn drills_read_answers_and_stats_are_kept_in_a_file() {
+    let mut stats = PlayerStats::new();
+    record(&mut stats, "sam", &["aim"], 0);
+    let items = stats.drill("sam", 0, 2);
+
+    let mut out = Vec::new();
+    let right = run_drill(&mut stats, "sam", &items, 0, Cursor::new("tamer\ntiger\n"), &mut out).unwrap();
+    assert_eq!(right, 1);
+    assert_eq!(String::from_utf8(out).unwrap(), "\
+1/2: T?MER (5 letters, 19 points)> Right.
+2/2: TIM?? (5 letters, 19 points)> It was TIMER.
+1 of 2 right.
+");
+
+    let path = std::env::temp_dir().join(format!("ruzzle_solver_{}_stats.tsv", std::process::id()));
+    let path = path.to_str().unwrap();
+    let _ = fs::remove_file(path);
+    assert_eq!(PlayerStats::read(path), Ok(PlayerStats::new()));
+    stats.write(path).unwrap();
+    assert_eq!(PlayerStats::read(path), Ok(stats));
+
+    fs::write(path, "sam\tTIME\t1\n").unwrap();
+    assert!(PlayerStats::read(path).is_err());
+}
+
+#[test]
+fn player_names_with_tabs_or_line_breaks_are_rejected() {
+    assert_eq!(check_player_name("sam smith"), Ok(()));
+    for name in ["sam\tsmith", "sam\nsmith", "sam\r"] {
+        assert!(check_player_name(name).is_err(), "{:?} was accepted", name);
+    }
+
+    let mut stats = PlayerStats::new();
+    record(&mut stats, "sam\tTIME", &[], 0);
+    let path = std::env::temp_dir().join(format!("ruzzle_solver_{}_bad_stats.tsv", std::process::id()));
+    let path = path.to_str().unwrap();
+    assert!(stats.write(path).is_err());
+    assert!(fs::metadata(path).is_err());
+}
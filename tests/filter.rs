@@ -0,0 +1,97 @@
+mod common;
+
+use ruzzle_solver::filter::{Filter, Glob, Multiplier};
+
+/// Words ending in -ING along the top row, with a triple word on the S in row 1, column 1, a
+/// double letter on the A in row 2, column 2 and a triple letter on the S in the bottom left
+/// corner.
+const BOARD: [&str; 2] = ["RING ASTE TEAM SLID", "---- -3-- --D- T---"];
+const WORDS: [&str; 9] = ["RAT", "RATS", "RING", "SING", "SLID", "STEAM", "STING", "TEAM", "ZING"];
+
+fn filtered_words(filter: &Filter) -> Vec<String> {
+    let mut board = common::board(&common::dictionary(&WORDS), &BOARD);
+    board.solve();
+    board.dedup_entries();
+    board.filter_entries(filter);
+    let mut words: Vec<String> = board.word_info.into_iter().map(|(word, _, _)| word).collect();
+    words.sort();
+    words
+}
+
+#[test]
+fn globs_match_wildcards_and_classes() {
+    let glob = |pattern: &str| Glob::new(pattern).unwrap();
+    assert!(glob("*ing").matches("HUNTING"));
+    assert!(glob("*ING").matches("ING"));
+    assert!(!glob("*ING").matches("HUNTER"));
+    assert!(glob("H?NT").matches("HUNT"));
+    assert!(!glob("H?NT").matches("HNT"));
+    assert!(glob("[HT]*").matches("TREE"));
+    assert!(!glob("[!HT]*").matches("TREE"));
+    assert!(glob("[!HT]*").matches("RUN"));
+    assert!(Glob::new("[AB").is_err());
+}
+
+#[test]
+fn parsed_filters_equal_built_filters() {
+    let parsed: Filter = "len=4..6 score=10.. word=HU* use=0,0 avoid=3,3 mult=DW".parse().unwrap();
+    let built = Filter::new()
+        .min_len(4).max_len(6)
+        .min_score(10)
+        .pattern(Glob::new("HU*").unwrap())
+        .uses_cell((0, 0))
+        .avoids_cell((3, 3))
+        .uses_multiplier(Multiplier::DoubleWord);
+    assert_eq!(parsed, built);
+    assert_eq!("".parse::<Filter>().unwrap(), Filter::new());
+
+    for invalid in ["len", "len=x", "use=4,0", "use=1", "mult=QW", "colour=red"] {
+        assert!(invalid.parse::<Filter>().is_err(), "{} should not parse", invalid);
+    }
+}
+
+#[test]
+fn filters_by_length_and_pattern() {
+    assert_eq!(filtered_words(&Filter::new()), ["RAT", "RATS", "RING", "SING", "SLID", "STEAM", "STING", "TEAM"]);
+    assert_eq!(filtered_words(&"len=5..".parse().unwrap()), ["STEAM", "STING"]);
+    assert_eq!(filtered_words(&"len=..3".parse().unwrap()), ["RAT"]);
+    assert_eq!(filtered_words(&"len=4".parse().unwrap()), ["RATS", "RING", "SING", "SLID", "TEAM"]);
+    assert_eq!(filtered_words(&"word=*ING".parse().unwrap()), ["RING", "SING", "STING"]);
+    assert_eq!(filtered_words(&"word=*ING len=5".parse().unwrap()), ["STING"]);
+    assert_eq!(filtered_words(&"word=[RS]?NG".parse().unwrap()), ["RING", "SING"]);
+    assert_eq!(filtered_words(&"word=?[!AEIOU]*".parse().unwrap()), ["SLID", "STEAM", "STING"]);
+}
+
+#[test]
+fn filters_by_cells_and_multipliers() {
+    // Each word is kept with its best path: RATS scores most by ending on the triple word.
+    assert_eq!(filtered_words(&"use=0,0".parse().unwrap()), ["RAT", "RATS", "RING"]);
+    assert_eq!(filtered_words(&"use=0,0 avoid=1,1".parse().unwrap()), ["RAT", "RING"]);
+    assert_eq!(filtered_words(&"mult=TW".parse().unwrap()), ["RATS", "SING", "STEAM", "STING"]);
+    assert_eq!(filtered_words(&"mult=DL".parse().unwrap()), ["STEAM", "TEAM"]);
+    assert_eq!(filtered_words(&"mult=TW mult=DL".parse().unwrap()), ["STEAM"]);
+    assert_eq!(filtered_words(&"mult=TL".parse().unwrap()), ["SLID"]);
+    assert_eq!(filtered_words(&"mult=DW".parse().unwrap()), Vec::<String>::new());
+}
+
+#[test]
+fn filters_by_score() {
+    assert_eq!(filtered_words(&"score=29".parse().unwrap()), ["STEAM"]);
+    assert_eq!(filtered_words(&Filter::new().max_score(6)), ["RAT", "RING"]);
+    assert_eq!(filtered_words(&Filter::new().min_score(8).max_score(25)), ["RATS", "SING"]);
+    assert_eq!(filtered_words(&Filter::new().min_score(19).max_score(25)), Vec::<String>::new());
+}
+
+#[test]
+fn parses_comparisons_as_ranges() {
+    let parse = |expression: &str| expression.parse::<Filter>().unwrap();
+    assert_eq!(parse("len>=6"), parse("len=6.."));
+    assert_eq!(parse("len>5"), parse("len=6.."));
+    assert_eq!(parse("len<=8 score<40"), parse("len=..8 score=..39"));
+    assert_eq!(filtered_words(&parse("len>=5")), ["STEAM", "STING"]);
+    assert_eq!(filtered_words(&parse("score>20")), ["STEAM", "STING"]);
+
+    for invalid in ["len<0", "len>255", "len>=256", "word>=A", "mult<TW", "len=>6"] {
+        assert!(invalid.parse::<Filter>().is_err(), "{} should not parse", invalid);
+    }
+}
@@ -0,0 +1,312 @@
+"""Draws the screenshots used to test reading boards from images, and the solver's glyph templates.
+
+The letters are rendered from TrueType fonts with anti-aliasing, using the small rasterizer
+below so that only the standard library is needed. The bundled templates are made from
+DejaVu Sans Bold, while the screenshots use other fonts and sizes, so the tests check that
+letters are recognised in fonts other than the one the templates came from.
+
+    python3 make_screenshots.py             draws the screenshots
+    python3 make_screenshots.py templates   prints the GLYPHS table of src/screenshot.rs
+"""
+import random
+import struct
+import sys
+import zlib
+
+FONT_DIR = "/usr/share/fonts/truetype/dejavu/"
+TEMPLATE_FONT = FONT_DIR + "DejaVuSans-Bold.ttf"
+
+# The letters of the built in alphabets, in the order of the GLYPHS table.
+LETTERS = "ABCDEFGHIJKLMNOPQRSTUVWXYZÄÅÑÖÜ"
+
+# As in src/screenshot.rs.
+GRID_WIDTH = 10
+GRID_HEIGHT = 14
+
+TILES = {
+    '-': (240, 230, 210),
+    'D': (90, 180, 90),
+    'T': (60, 120, 200),
+    '2': (240, 170, 50),
+    '3': (210, 60, 60),
+}
+
+BACKGROUND = (60, 40, 90)
+INK = (30, 30, 30)
+
+# Samples per pixel along each axis, for anti-aliasing.
+SAMPLES = 4
+
+
+class Font:
+    """The outlines of the glyphs of a TrueType font."""
+
+    def __init__(self, path):
+        with open(path, "rb") as f:
+            self.data = f.read()
+        num_tables = struct.unpack_from(">H", self.data, 4)[0]
+        self.tables = {}
+        for i in range(num_tables):
+            tag, _, offset, length = struct.unpack_from(">4sIII", self.data, 12 + 16 * i)
+            self.tables[tag.decode()] = offset
+        head = self.tables["head"]
+        self.long_loca = struct.unpack_from(">h", self.data, head + 50)[0] == 1
+        self.cmap = self.read_cmap()
+
+    def read_cmap(self):
+        """Maps the characters of the Basic Multilingual Plane to glyph indices."""
+        cmap = self.tables["cmap"]
+        count = struct.unpack_from(">H", self.data, cmap + 2)[0]
+        for i in range(count):
+            platform, encoding, offset = struct.unpack_from(">HHI", self.data, cmap + 4 + 8 * i)
+            if (platform, encoding) == (3, 1):
+                break
+        else:
+            raise ValueError("The font has no Unicode character map.")
+        table = cmap + offset
+        segments = struct.unpack_from(">H", self.data, table + 6)[0] // 2
+        ends = struct.unpack_from(">%dH" % segments, self.data, table + 14)
+        starts = struct.unpack_from(">%dH" % segments, self.data, table + 16 + 2 * segments)
+        deltas = struct.unpack_from(">%dh" % segments, self.data, table + 16 + 4 * segments)
+        range_offsets_at = table + 16 + 6 * segments
+        range_offsets = struct.unpack_from(">%dH" % segments, self.data, range_offsets_at)
+        mapping = {}
+        for i in range(segments):
+            for code in range(starts[i], ends[i] + 1):
+                if code == 0xFFFF:
+                    continue
+                if range_offsets[i] == 0:
+                    glyph = (code + deltas[i]) & 0xFFFF
+                else:
+                    at = range_offsets_at + 2 * i + range_offsets[i] + 2 * (code - starts[i])
+                    glyph = struct.unpack_from(">H", self.data, at)[0]
+                    glyph = (glyph + deltas[i]) & 0xFFFF if glyph else 0
+                mapping[chr(code)] = glyph
+        return mapping
+
+    def glyph_offset(self, index):
+        loca = self.tables["loca"]
+        if self.long_loca:
+            start, end = struct.unpack_from(">II", self.data, loca + 4 * index)
+        else:
+            start, end = (2 * v for v in struct.unpack_from(">HH", self.data, loca + 2 * index))
+        return (self.tables["glyf"] + start, end - start)
+
+    def contours(self, index):
+        """Returns the contours of a glyph, each a list of (x, y, on_curve) points in font units."""
+        offset, length = self.glyph_offset(index)
+        if length == 0:
+            return []
+        count = struct.unpack_from(">h", self.data, offset)[0]
+        at = offset + 10
+        if count < 0:
+            return self.composite_contours(at)
+
+        ends = struct.unpack_from(">%dH" % count, self.data, at)
+        at += 2 * count
+        at += 2 + struct.unpack_from(">H", self.data, at)[0]
+        points = ends[-1] + 1 if ends else 0
+        flags = []
+        while len(flags) < points:
+            flag = self.data[at]
+            at += 1
+            flags.append(flag)
+            if flag & 8:
+                flags.extend([flag] * self.data[at])
+                at += 1
+
+        def coordinates(short_bit, same_bit):
+            nonlocal at
+            values, value = [], 0
+            for flag in flags:
+                if flag & short_bit:
+                    delta = self.data[at]
+                    at += 1
+                    value += delta if flag & same_bit else -delta
+                elif not flag & same_bit:
+                    value += struct.unpack_from(">h", self.data, at)[0]
+                    at += 2
+                values.append(value)
+            return values
+
+        xs = coordinates(2, 16)
+        ys = coordinates(4, 32)
+        contours, start = [], 0
+        for end in ends:
+            contours.append([(xs[i], ys[i], flags[i] & 1) for i in range(start, end + 1)])
+            start = end + 1
+        return contours
+
+    def composite_contours(self, at):
+        contours = []
+        while True:
+            flags, index = struct.unpack_from(">HH", self.data, at)
+            at += 4
+            if flags & 1:
+                dx, dy = struct.unpack_from(">hh", self.data, at)
+                at += 4
+            else:
+                dx, dy = struct.unpack_from(">bb", self.data, at)
+                at += 2
+            if not flags & 2:
+                raise ValueError("Composite glyphs placed by point numbers are not supported.")
+            scale = (1, 0, 0, 1)
+            if flags & 8:
+                s = struct.unpack_from(">h", self.data, at)[0] / 16384
+                scale, at = (s, 0, 0, s), at + 2
+            elif flags & 0x40:
+                sx, sy = (v / 16384 for v in struct.unpack_from(">hh", self.data, at))
+                scale, at = (sx, 0, 0, sy), at + 4
+            elif flags & 0x80:
+                scale, at = tuple(v / 16384 for v in struct.unpack_from(">hhhh", self.data, at)), at + 8
+            a, b, c, d = scale
+            for contour in self.contours(index):
+                contours.append([(a * x + c * y + dx, b * x + d * y + dy, on) for x, y, on in contour])
+            if not flags & 0x20:
+                return contours
+
+    def outline(self, letter):
+        """Returns the edges of letter's outline, in font units with y upwards, flattening curves."""
+        edges = []
+        for contour in self.contours(self.cmap[letter]):
+            # Insert the on curve points implied between consecutive off curve points.
+            points = []
+            for i, (x, y, on) in enumerate(contour):
+                px, py, pon = contour[i - 1]
+                if not on and not pon:
+                    points.append(((x + px) / 2, (y + py) / 2, 1))
+                points.append((x, y, on))
+            start = next(i for i, point in enumerate(points) if point[2])
+            points = points[start:] + points[:start] + [points[start]]
+            line, i = [points[0][:2]], 1
+            while i < len(points):
+                x, y, on = points[i]
+                if on:
+                    line.append((x, y))
+                    i += 1
+                else:
+                    (x0, y0), (x2, y2) = line[-1], points[i + 1][:2]
+                    for step in range(1, 9):
+                        t = step / 8
+                        line.append(((1 - t) ** 2 * x0 + 2 * (1 - t) * t * x + t * t * x2,
+                                     (1 - t) ** 2 * y0 + 2 * (1 - t) * t * y + t * t * y2))
+                    i += 2
+            edges.extend(zip(line, line[1:]))
+        return edges
+
+
+def rasterize(font, letter, height):
+    """Renders letter so that its ink is height pixels tall, returning its coverage of each
+    pixel, from 0 to 1, row by row."""
+    edges = font.outline(letter)
+    ys = [y for edge in edges for _, y in edge]
+    xs = [x for edge in edges for x, _ in edge]
+    scale = height / (max(ys) - min(ys))
+    left, top = min(xs), max(ys)
+    edges = [((scale * (x0 - left), scale * (top - y0)), (scale * (x1 - left), scale * (top - y1)))
+             for (x0, y0), (x1, y1) in edges]
+    width = int(scale * (max(xs) - left)) + 1
+    rows = int(height) + 1
+
+    coverage = [[0.0] * width for _ in range(rows)]
+    for sy in range(rows * SAMPLES):
+        y = (sy + 0.5) / SAMPLES
+        crossings = []
+        for (x0, y0), (x1, y1) in edges:
+            if (y0 <= y < y1) or (y1 <= y < y0):
+                crossings.append((x0 + (y - y0) * (x1 - x0) / (y1 - y0), 1 if y1 > y0 else -1))
+        crossings.sort()
+        winding = 0
+        for (x, direction), (next_x, _) in zip(crossings, crossings[1:] + [(0, 0)]):
+            winding += direction
+            if winding == 0:
+                continue
+            for sx in range(int(x * SAMPLES + 0.5), int(next_x * SAMPLES + 0.5)):
+                coverage[sy // SAMPLES][min(sx // SAMPLES, width - 1)] += 1 / (SAMPLES * SAMPLES)
+    return coverage
+
+
+def glyph_features(mask):
+    """The fraction of ink in each cell of a grid laid over the ink's bounding box, as in
+    src/screenshot.rs."""
+    cells = [(x, y) for y, row in enumerate(mask) for x, ink in enumerate(row) if ink]
+    left, right = min(x for x, _ in cells), max(x for x, _ in cells)
+    top, bottom = min(y for _, y in cells), max(y for _, y in cells)
+    box_width, box_height = right - left + 1, bottom - top + 1
+    features = []
+    for gy in range(GRID_HEIGHT):
+        y0 = top + int(gy * box_height / GRID_HEIGHT)
+        y1 = max(top + -int(-(gy + 1) * box_height // GRID_HEIGHT), y0 + 1)
+        row = []
+        for gx in range(GRID_WIDTH):
+            x0 = left + int(gx * box_width / GRID_WIDTH)
+            x1 = max(left + -int(-(gx + 1) * box_width // GRID_WIDTH), x0 + 1)
+            inked = sum(mask[y][x] for y in range(y0, y1) for x in range(x0, x1))
+            row.append(inked / ((y1 - y0) * (x1 - x0)))
+        features.append(row)
+    return features
+
+
+def print_templates():
+    font = Font(TEMPLATE_FONT)
+    print("const GLYPHS: [(char, [u16; GRID_HEIGHT]); %d] = [" % len(LETTERS))
+    for letter in LETTERS:
+        mask = [[c >= 0.5 for c in row] for row in rasterize(font, letter, 140)]
+        rows = ["0b" + "".join("1" if cell >= 0.5 else "0" for cell in row) for row in glyph_features(mask)]
+        print("    ('%s', [%s])," % (letter, ", ".join(rows)))
+    print("];")
+
+
+def draw(board, mults, font, path, width=400, height=700, region=(20, 200, 360), seed=0):
+    rng = random.Random(seed)
+    font = Font(FONT_DIR + font)
+    pixels = [[BACKGROUND] * width for _ in range(height)]
+    left, top, size = region
+    cell = size // 4
+
+    for row in range(4):
+        for col in range(4):
+            x0, y0 = left + col * cell, top + row * cell
+            tile = TILES[mults[row][col]]
+            for y in range(y0 + 4, y0 + cell - 4):
+                for x in range(x0 + 4, x0 + cell - 4):
+                    pixels[y][x] = tile
+
+            # Capital letters are about 40% of the tile's height, and accents add to that.
+            letter = board[row][col]
+            glyph = rasterize(font, letter, cell * (0.5 if letter in "ÄÅÑÖÜ" else 0.4))
+            gx, gy = x0 + (cell - len(glyph[0])) // 2, y0 + (cell - len(glyph)) // 2
+            for j, coverage_row in enumerate(glyph):
+                for i, coverage in enumerate(coverage_row):
+                    coverage = min(coverage, 1)
+                    pixels[gy + j][gx + i] = tuple(round(t * (1 - coverage) + k * coverage) for t, k in zip(tile, INK))
+
+    noisy = bytearray()
+    for y, row in enumerate(pixels):
+        noisy.append(0)
+        for x, pixel in enumerate(row):
+            if left <= x < left + size and top <= y < top + size:
+                noisy.extend(max(0, min(255, c + rng.randint(-3, 3))) for c in pixel)
+            else:
+                noisy.extend(pixel)
+
+    def chunk(kind, data):
+        return struct.pack(">I", len(data)) + kind + data + struct.pack(">I", zlib.crc32(kind + data))
+
+    with open(path, "wb") as f:
+        f.write(b"\x89PNG\r\n\x1a\n")
+        f.write(chunk(b"IHDR", struct.pack(">IIBBBBB", width, height, 8, 2, 0, 0, 0)))
+        f.write(chunk(b"IDAT", zlib.compress(bytes(noisy), 9)))
+        f.write(chunk(b"IEND", b""))
+
+
+if __name__ == "__main__":
+    if sys.argv[1:] == ["templates"]:
+        print_templates()
+    else:
+        draw(["HHSA", "UIRN", "NTAE", "ERIM"], ["2---", "3-T-", "--2D", "-T--"],
+             "DejaVuSansCondensed-Bold.ttf", "board.png", seed=1)
+        draw(["BCDF", "GJKL", "OPQV", "WXYZ"], ["D-T-", "-2-3", "----", "T-D2"],
+             "DejaVuSansMono-Bold.ttf", "alphabet.png", seed=2)
+        draw(["ÄÖÜÅ", "ÑAOU", "NSTE", "RLIM"], ["----", "-D--", "--3-", "T---"],
+             "DejaVuSansCondensed-Bold.ttf", "accents.png", seed=3)
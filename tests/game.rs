@@ -0,0 +1,100 @@
+mod common;
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use ruzzle_solver::Board;
+use ruzzle_solver::dictionary::Dictionary;
+use ruzzle_solver::game::{Match, MatchResult, ROUNDS, play_turn};
+use ruzzle_solver::trainer::Guess;
+
+/// The letters of every round's board. The letter multipliers of the second round fall on the L
+/// and the E ending WAVE, and the third round adds a triple word on the D of DUNE.
+const LETTERS: &str = "BARN OILS WAVE DUNE";
+const WORDS: [&str; 6] = ["BAR", "DUNE", "OIL", "WAVE", "WAVES", "ZEBRA"];
+
+fn board(dictionary: &Arc<Dictionary>, mults: &str) -> Board {
+    common::board(dictionary, &[LETTERS, mults])
+}
+
+fn test_match(players: &[&str]) -> Match {
+    let dictionary = common::dictionary(&WORDS);
+    let boards = vec![board(&dictionary, "---- ---- ---- ----"), board(&dictionary, "---- --D- ---T ----"),
+                      board(&dictionary, "---- --D- ---T 3---")];
+    Match::new(players, boards).unwrap()
+}
+
+#[test]
+fn scores_each_word_once_per_round() {
+    let mut game = test_match(&["ann", "bob"]);
+    assert_eq!(game.guess(0, "waves"), Guess::Scored(16));
+    assert_eq!(game.guess(0, "WAVES"), Guess::Repeated);
+    assert_eq!(game.guess(1, "waves"), Guess::Scored(16));
+    assert_eq!(game.guess(1, "zebra"), Guess::NotInBoard);
+    assert_eq!(game.guess(1, "bran"), Guess::NotAWord);
+    game.end_round();
+
+    assert_eq!(game.guess(0, "oil"), Guess::Scored(4));
+    game.end_round();
+    assert_eq!(game.guess(0, "dune"), Guess::Scored(24));
+    assert_eq!(game.guess(1, "dune"), Guess::Scored(24));
+    assert_eq!(game.guess(1, "wave"), Guess::Scored(12));
+    game.end_round();
+    assert!(game.is_over());
+    assert_eq!(game.guess(0, "wave"), Guess::NotInBoard);
+
+    let result = game.result();
+    assert_eq!(result.scores, [[16, 4, 24], [16, 0, 36]]);
+    assert_eq!(result.winners, [1]);
+    let mut out = Vec::new();
+    result.write(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "\
+Player       Round 1  Round 2  Round 3    Total
+ann               16        4       24       44
+bob               16        0       36       52
+bob wins with 52 points.
+");
+}
+
+#[test]
+fn generates_boards_with_more_multipliers_each_round() {
+    let dictionary = common::dictionary(&WORDS);
+    let game = Match::generate(&["ann"], &dictionary, 3).unwrap();
+    let again = Match::generate(&["ann"], &dictionary, 3).unwrap();
+
+    for round in 0..ROUNDS {
+        assert_eq!(game.board(round).board, again.board(round).board);
+    }
+    let count = |tiles: &[u8; 16]| tiles.iter().filter(|&&mult| mult > 1).count();
+    assert_eq!((count(&game.board(0).letter_mults), count(&game.board(0).word_int_mults)), (0, 0));
+    assert_eq!((count(&game.board(1).letter_mults), count(&game.board(1).word_int_mults)), (3, 0));
+    assert_eq!((count(&game.board(2).letter_mults), count(&game.board(2).word_int_mults)), (3, 2));
+}
+
+#[test]
+fn turns_end_at_an_empty_line() {
+    let mut game = test_match(&["ann", "bob"]);
+    let mut input = Cursor::new("wave\nzebra\n\noil\n");
+    let mut out = Vec::new();
+    assert_eq!(play_turn(&mut game, 0, Duration::from_secs(60), &mut input, &mut out).unwrap(), 10);
+    assert_eq!(play_turn(&mut game, 1, Duration::from_secs(60), &mut input, &mut out).unwrap(), 3);
+    assert!(String::from_utf8(out).unwrap().starts_with(
+        "ann, you have 60s. Type one word per line, and an empty line when you are done.\n+10\nThat word is not in the board.\n"));
+}
+
+#[test]
+fn rejects_matches_without_players_or_three_boards() {
+    let dictionary = common::dictionary(&WORDS);
+    assert!(Match::new(&[], vec![]).is_err());
+    assert_eq!(Match::new(&["ann"], vec![board(&dictionary, "---- ---- ---- ----")]).err().unwrap(),
+               "A match needs 3 boards, one for each round, not 1.");
+}
+
+#[test]
+fn reports_draws() {
+    let result = MatchResult { players: vec!["ann".to_string(), "bob".to_string()], scores: vec![[1, 2, 3], [3, 2, 1]],
+                               winners: vec![0, 1] };
+    let mut out = Vec::new();
+    result.write(&mut out).unwrap();
+    assert!(String::from_utf8(out).unwrap().ends_with("ann and bob draw with 6 points.\n"));
+}
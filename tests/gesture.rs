@@ -0,0 +1,71 @@
+use ruzzle_solver::{path_to_vec, vec_to_path};
+use ruzzle_solver::gesture::{BoardGeometry, GestureFormat, write_gestures};
+
+const GEOMETRY: BoardGeometry = BoardGeometry { origin: (100, 500), pitch: 200 };
+
+fn words() -> Vec<(String, u16, u64)> {
+    vec![
+        ("HUH".to_string(), 60, vec_to_path(&[(0, 1), (1, 0), (0, 0)])),
+        ("UH".to_string(), 36, vec_to_path(&[(1, 0), (0, 0)])),
+    ]
+}
+
+fn render(format: GestureFormat) -> String {
+    let mut out = Vec::new();
+    write_gestures(&mut out, &words(), &GEOMETRY, format, None).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn vec_to_path_inverts_path_to_vec() {
+    let cells = vec![(3, 3), (2, 2), (1, 2), (0, 3)];
+    assert_eq!(path_to_vec(vec_to_path(&cells)), cells);
+}
+
+#[test]
+fn swipe_points_use_rows_for_y_and_columns_for_x() {
+    let points = GEOMETRY.swipe_points(vec_to_path(&[(0, 1), (1, 0), (3, 2)]));
+    assert_eq!(points, vec![(300, 500), (100, 700), (500, 1100)]);
+}
+
+#[test]
+fn json_lists_each_word_with_its_points() {
+    assert_eq!(render(GestureFormat::Json), "[\n\
+        \x20 {\"word\": \"HUH\", \"score\": 60, \"points\": [[300, 500], [100, 700], [100, 500]]},\n\
+        \x20 {\"word\": \"UH\", \"score\": 36, \"points\": [[100, 700], [100, 500]]}\n\
+        ]\n");
+}
+
+#[test]
+fn json_escapes_words() {
+    let words = [("SAY \"HI\\".to_string(), 3, vec_to_path(&[(0, 0), (0, 1)]))];
+    let mut out = Vec::new();
+    write_gestures(&mut out, &words, &GEOMETRY, GestureFormat::Json, None).unwrap();
+    assert!(String::from_utf8(out).unwrap().contains(r#"{"word": "SAY \"HI\\", "score": 3,"#));
+}
+
+#[test]
+fn adb_script_presses_moves_and_releases() {
+    assert_eq!(render(GestureFormat::Adb), "#!/bin/sh\n\
+        # HUH (60)\n\
+        adb shell input motionevent DOWN 300 500\n\
+        sleep 0.04\n\
+        adb shell input motionevent MOVE 100 700\n\
+        sleep 0.04\n\
+        adb shell input motionevent MOVE 100 500\n\
+        adb shell input motionevent UP 100 500\n\
+        # UH (36)\n\
+        adb shell input motionevent DOWN 100 700\n\
+        sleep 0.04\n\
+        adb shell input motionevent MOVE 100 500\n\
+        adb shell input motionevent UP 100 500\n");
+}
+
+#[test]
+fn xdotool_script_drags_through_each_cell() {
+    assert_eq!(render(GestureFormat::Xdotool), "#!/bin/sh\n\
+        # HUH (60)\n\
+        xdotool mousemove 300 500 mousedown 1 sleep 0.04 mousemove 100 700 sleep 0.04 mousemove 100 500 mouseup 1\n\
+        # UH (36)\n\
+        xdotool mousemove 100 700 mousedown 1 sleep 0.04 mousemove 100 500 mouseup 1\n");
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::sync::Arc;
+use ruzzle_solver::{Board, parse_board_and_mults, vec_to_path};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::{Dictionary, Registry, Store};
+use ruzzle_solver::gesture::{BoardGeometry, GestureFormat, write_gestures};
+use ruzzle_solver::metadata::{Metadata, WordInfo, json_string};
+use ruzzle_solver::store::HashStore;
+
+const METADATA: &str = "\
+# word\trank\tpart of speech\tdefinition
+qat\t41000\tnoun\tAn evergreen shrub whose leaves are chewed as a \"stimulant\".
+HUT\t\tnoun
+UH\t900
+";
+
+fn write_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("ruzzle_solver_{}_{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn metadata() -> Metadata {
+    Metadata::read(&write_file("metadata.tsv", METADATA), Alphabet::english()).unwrap()
+}
+
+#[test]
+fn metadata_is_read_with_missing_fields() {
+    let metadata = metadata();
+    assert_eq!(metadata.len(), 3);
+    assert_eq!(metadata.get("QAT"), Some(&WordInfo {
+        definition: Some("An evergreen shrub whose leaves are chewed as a \"stimulant\".".to_string()),
+        frequency_rank: Some(41000),
+        part_of_speech: Some("noun".to_string()),
+    }));
+    assert_eq!(metadata.get("hut"), Some(&WordInfo { part_of_speech: Some("noun".to_string()), ..WordInfo::default() }));
+    assert_eq!(metadata.get("UH").unwrap().frequency_rank, Some(900));
+    assert_eq!(metadata.get("HUH"), None);
+}
+
+#[test]
+fn bad_frequency_ranks_are_reported() {
+    let path = write_file("bad_metadata.tsv", "QAT\tcommon\tnoun\n");
+    assert!(Metadata::read(&path, Alphabet::english()).is_err());
+}
+
+#[test]
+fn json_strings_are_escaped() {
+    assert_eq!(json_string("a \"b\"\\\n\tc"), "\"a \\\"b\\\"\\\\\\n\\tc\"");
+}
+
+#[test]
+fn solutions_are_annotated_in_json() {
+    let alphabet = Alphabet::english();
+    let words = ["QAT", "HUT", "UH"].iter().map(|word| alphabet.encode(word).unwrap()).collect();
+    let lines: Vec<String> = ["Q A T X", "H U X X", "X X X X", "X X X X", "",
+                              "- - - -", "- - - -", "- - - -", "- - - -"]
+        .iter().map(|line| line.to_string()).collect();
+    let (board, word_int_mults, letter_mults) = parse_board_and_mults(lines, &alphabet);
+    let dictionary = Dictionary::new("test", alphabet, Store::Hashed(HashStore::from_words(words, 5)))
+        .with_metadata(metadata());
+    let mut board = Board::new(board, word_int_mults, letter_mults, Arc::new(dictionary));
+    board.solve();
+    board.word_info.retain(|(word, _, _)| word == "QAT" || word == "UH");
+    board.word_info.sort();
+
+    let mut out = Vec::new();
+    board.write_json(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "[
+  {\"word\": \"QAT\", \"score\": 12, \"path\": [[0, 0], [0, 1], [0, 2]], \"definition\": \"An evergreen shrub whose leaves are chewed as a \\\"stimulant\\\".\", \"frequency_rank\": 41000, \"part_of_speech\": \"noun\"},
+  {\"word\": \"UH\", \"score\": 6, \"path\": [[1, 1], [1, 0]], \"frequency_rank\": 900}
+]
+");
+}
+
+#[test]
+fn gestures_are_annotated_in_json() {
+    let words = vec![("HUT".to_string(), 42, vec_to_path(&[(1, 0), (1, 1), (0, 2)]))];
+    let geometry = BoardGeometry { origin: (0, 0), pitch: 10 };
+    let mut out = Vec::new();
+    write_gestures(&mut out, &words, &geometry, GestureFormat::Json, Some(&metadata())).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(),
+               "[\n  {\"word\": \"HUT\", \"score\": 42, \"points\": [[0, 10], [10, 10], [20, 0]], \"part_of_speech\": \"noun\"}\n]\n");
+}
+
+#[test]
+fn metadata_is_loaded_next_to_the_dictionary() {
+    let words = write_file("metadata_words.txt", "QAT\nHUT\n");
+    let metadata = write_file("metadata_config.tsv", METADATA);
+    let config = write_file("metadata_dictionaries.txt", &format!("small en words {} metadata {}\n", words, metadata));
+    let mut registry = Registry::read_config(&config).unwrap();
+    let dictionary = registry.get("small").unwrap();
+    assert_eq!(dictionary.metadata().unwrap().get("QAT").unwrap().frequency_rank, Some(41000));
+}
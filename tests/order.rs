@@ -0,0 +1,79 @@
+mod common;
+
+use ruzzle_solver::order::{Grouping, SortKey, SortOrder};
+
+/// Words which tie in pairs: CODE and SPED on score and length, CAT and COT on score, length,
+/// start cell and swipe length, and TIDE and TIED on everything but their swipe.
+const BOARD: [&str; 2] = ["CATS ODER TIPS NEWT", "---- ---- ---- ----"];
+const WORDS: [&str; 6] = ["CAT", "CODE", "COT", "SPED", "TIDE", "TIED"];
+
+fn solved_board() -> ruzzle_solver::Board {
+    let mut board = common::board(&common::dictionary(&WORDS), &BOARD);
+    board.solve();
+    board.dedup_entries();
+    board
+}
+
+fn sorted_words(keys: &str) -> Vec<String> {
+    let mut board = solved_board();
+    board.sort_entries_by(&keys.parse().unwrap());
+    board.word_info.into_iter().map(|(word, _, _)| word).collect()
+}
+
+#[test]
+fn sorts_by_each_key_with_tiebreakers() {
+    // Ties are broken by score, then by word.
+    assert_eq!(sorted_words("score"), ["CODE", "SPED", "CAT", "COT", "TIDE", "TIED"]);
+    assert_eq!(sorted_words("length"), ["CODE", "SPED", "TIDE", "TIED", "CAT", "COT"]);
+    assert_eq!(sorted_words("alpha"), ["CAT", "CODE", "COT", "SPED", "TIDE", "TIED"]);
+    assert_eq!(sorted_words("start"), ["CODE", "CAT", "COT", "TIDE", "TIED", "SPED"]);
+    // TIED turns a corner diagonally, so its swipe is the longest.
+    assert_eq!(sorted_words("path"), ["CAT", "COT", "CODE", "SPED", "TIDE", "TIED"]);
+    assert_eq!(sorted_words("start,alpha"), ["CAT", "CODE", "COT", "TIDE", "TIED", "SPED"]);
+    assert_eq!(sorted_words("length,alpha"), ["CODE", "SPED", "TIDE", "TIED", "CAT", "COT"]);
+    assert_eq!(sorted_words("path,length"), ["CAT", "COT", "CODE", "SPED", "TIDE", "TIED"]);
+}
+
+#[test]
+fn parses_sort_orders() {
+    assert_eq!("length, score".parse::<SortOrder>().unwrap(), SortOrder::new(vec![SortKey::Length, SortKey::Score]));
+    assert_eq!(SortOrder::default(), SortOrder::new(vec![SortKey::Score]));
+    assert!("length,size".parse::<SortOrder>().is_err());
+    assert!("".parse::<SortOrder>().is_err());
+}
+
+#[test]
+fn groups_by_length_and_start_cell() {
+    let mut board = solved_board();
+    board.sort_entries_by(&"alpha".parse().unwrap());
+
+    let labels = |grouping| -> Vec<(String, Vec<String>)> {
+        board.groups(grouping).into_iter()
+            .map(|(label, entries)| (label, entries.iter().map(|entry| entry.0.clone()).collect()))
+            .collect()
+    };
+    let words = |words: &[&str]| -> Vec<String> { words.iter().map(|word| word.to_string()).collect() };
+    assert_eq!(labels(Grouping::Length), [
+        ("4 letters".to_string(), words(&["CODE", "SPED", "TIDE", "TIED"])),
+        ("3 letters".to_string(), words(&["CAT", "COT"])),
+    ]);
+    assert_eq!(labels(Grouping::StartCell), [
+        ("Starting at (0, 0)".to_string(), words(&["CAT", "CODE", "COT"])),
+        ("Starting at (2, 0)".to_string(), words(&["TIDE", "TIED"])),
+        ("Starting at (2, 3)".to_string(), words(&["SPED"])),
+    ]);
+
+    let mut out = Vec::new();
+    board.write_groups(&mut out, Grouping::StartCell).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "Starting at (0, 0) (3 words)\n\
+        CAT, 6, [(0, 0), (0, 1), (0, 2)]\n\
+        CODE, 8, [(0, 0), (1, 0), (1, 1), (1, 2)]\n\
+        COT, 6, [(0, 0), (1, 0), (2, 0)]\n\
+        \n\
+        Starting at (2, 0) (2 words)\n\
+        TIDE, 5, [(2, 0), (2, 1), (1, 1), (1, 2)]\n\
+        TIED, 5, [(2, 0), (2, 1), (1, 2), (1, 1)]\n\
+        \n\
+        Starting at (2, 3) (1 words)\n\
+        SPED, 8, [(2, 3), (2, 2), (1, 2), (1, 1)]\n");
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use ruzzle_solver::{path_to_vec, vec_to_path};
+use ruzzle_solver::plan::{PlannedWord, TimeModel, plan_round};
+
+fn dense_words() -> Vec<(String, u16, u64)> {
+    let mut board = common::dense_board();
+    board.solve();
+    board.dedup_entries();
+    board.word_info
+}
+
+/// Returns the time taken to enter the words of plan in order, by the model.
+fn replay(plan: &[PlannedWord], model: &TimeModel) -> f32 {
+    let paths: Vec<Vec<(u8, u8)>> = plan.iter().map(|word| path_to_vec(word.path)).collect();
+    let swipes: f32 = paths.iter().map(|path| model.swipe_time(path)).sum();
+    let moves: f32 = paths.windows(2).map(|pair| model.move_time(*pair[0].last().unwrap(), pair[1][0])).sum();
+    swipes + moves
+}
+
+#[test]
+fn plans_fit_in_the_time_budget() {
+    let words = dense_words();
+    let model = TimeModel::default();
+    for budget in [0., 1., 10., 30., 90.] {
+        let plan = plan_round(&words, &model, budget);
+        let time = replay(&plan, &model);
+        assert!(time <= budget + 1e-3, "a plan for {}s takes {}s", budget, time);
+        assert!(plan.last().is_none_or(|last| (last.cumulative_time - time).abs() < 1e-3));
+    }
+    assert!(plan_round(&words, &model, 0.).is_empty());
+    assert!(plan_round(&words, &model, 30.).len() > 10);
+}
+
+#[test]
+fn plans_accumulate_time_and_score_in_order() {
+    let words = dense_words();
+    let plan = plan_round(&words, &TimeModel::default(), 60.);
+    assert!(plan.windows(2).all(|pair| pair[0].cumulative_time <= pair[1].cumulative_time));
+    assert!(plan.windows(2).all(|pair| pair[0].cumulative_score <= pair[1].cumulative_score));
+
+    let last = plan.last().unwrap();
+    assert_eq!(last.cumulative_score, plan.iter().map(|word| word.score as u32).sum::<u32>());
+    let distinct: std::collections::HashSet<&str> = plan.iter().map(|word| word.word.as_str()).collect();
+    assert_eq!(distinct.len(), plan.len());
+}
+
+#[test]
+fn moves_between_words_change_the_order() {
+    // FIRST ends in the top left corner. FAR and NEAR take as long to swipe and score the same,
+    // but FAR starts in the opposite corner.
+    let words = vec![
+        ("FIRST".to_string(), 50, vec_to_path(&[(0, 1), (0, 0)])),
+        ("FAR".to_string(), 10, vec_to_path(&[(3, 3), (3, 2)])),
+        ("NEAR".to_string(), 10, vec_to_path(&[(1, 0), (2, 0)])),
+    ];
+    let order = |per_move| -> Vec<String> {
+        let model = TimeModel { per_word: 0.5, per_cell: 0.15, per_move };
+        plan_round(&words, &model, 60.).into_iter().map(|word| word.word).collect()
+    };
+    assert_eq!(order(0.), ["FIRST", "FAR", "NEAR"]);
+    assert_eq!(order(0.5), ["FIRST", "NEAR", "FAR"]);
+}
+
+#[test]
+fn time_models_which_take_no_time_are_rejected() {
+    let model: TimeModel = "0.5, 0.15, 0.1".parse().unwrap();
+    assert_eq!((model.per_word, model.per_cell, model.per_move), (0.5, 0.15, 0.1));
+    for model in ["0,0,0.1", "0.5,-0.1,0.1", "0.5,0.1", "0.5,0.1,NaN", "0.5,0.1,inf", "a,b,c"] {
+        assert!(model.parse::<TimeModel>().is_err(), "{} was accepted", model);
+    }
+
+    // A model built by hand which takes no time plans every word, rather than dropping any.
+    let words = dense_words();
+    let plan = plan_round(&words, &TimeModel { per_word: 0., per_cell: 0., per_move: 0. }, 1.);
+    assert_eq!(plan.len(), words.len());
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::io::Cursor;
+use ruzzle_solver::play::{Flow, Session, run_repl};
+use ruzzle_solver::pretty::PrettyStyle;
+
+/// A triple word on the L, which starts LEARN and is the second letter of PLAN, and a double
+/// letter on the U. SEAL is a word, but not in the board.
+const BOARD: [&str; 2] = ["MOST PLAN EARS GLUE", "---- -3-- ---- --D-"];
+const WORDS: [&str; 6] = ["EAR", "GLUE", "LEARN", "PLAN", "SEAL", "ZEBRA"];
+
+fn session() -> Session {
+    Session::new(common::dictionary(&WORDS), PrettyStyle::Plain)
+}
+
+fn execute(session: &mut Session, command: &str) -> (Flow, String) {
+    let mut out = Vec::new();
+    let flow = session.execute(command, &mut out).unwrap();
+    (flow, String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn answers_commands_about_the_board() {
+    let mut session = session();
+    assert_eq!(session.load_board(&BOARD.join("\n")), Ok(4));
+
+    assert_eq!(execute(&mut session, "top 2").1, "  1. PLAN           21\n  2. LEARN          20\n");
+    assert!(execute(&mut session, "find learn").1.starts_with("LEARN scores 20 points:\n+------+"));
+    assert_eq!(execute(&mut session, "find seal").1, "SEAL is a word, but it is not in the board.\n");
+    assert_eq!(execute(&mut session, "find plane").1, "PLANE is not in the test dictionary.\n");
+
+    assert_eq!(execute(&mut session, "filter len<=4 score>5").1, "2 words match the filter.\n");
+    assert_eq!(execute(&mut session, "top").1, "  1. PLAN           21\n  2. GLUE            9\n");
+    assert_eq!(execute(&mut session, "filter").1, "4 words match the filter.\n");
+    assert_eq!(execute(&mut session, "filter len").1, "\"len\" is not a filter condition.\n");
+
+    assert_eq!(execute(&mut session, "next board").0, Flow::NextBoard);
+    assert_eq!(execute(&mut session, "quit").0, Flow::Quit);
+    assert!(execute(&mut session, "dance").1.starts_with("dance is not a command."));
+}
+
+#[test]
+fn loads_boards_without_multipliers_and_rejects_bad_boards() {
+    let mut session = session();
+    assert_eq!(session.load_board(BOARD[0]), Ok(4));
+    assert_eq!(session.board().unwrap().word_int_mults, [1; 16]);
+    assert!(session.load_board("MOST PLAN").is_err());
+    assert!(session.load_board("MOST PLAN EARS GLU1").is_err());
+    // A failed load keeps the previous board.
+    assert_eq!(session.words().len(), 4);
+}
+
+#[test]
+fn prompt_reads_boards_and_commands() {
+    let mut session = session();
+    let input = "MOST PLAN\nEARS GLUE\n\ntop 1\nnext board\nMOST PLAN EARS\nGLUE ---- -3-- ---- --D-\nfind learn\nquit\ntop\n";
+    let mut out = Vec::new();
+    run_repl(&mut session, Cursor::new(input), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    assert!(out.contains("4 words were found."));
+    // Without multipliers LEARN scores 10, and with them 20.
+    assert!(out.contains("  1. LEARN          10\n"));
+    assert!(out.contains("LEARN scores 20 points:"));
+    assert!(out.ends_with("> "), "the prompt stops at quit");
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use ruzzle_solver::{Board, parse_board_and_mults, vec_to_path};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::{Dictionary, Store};
+use ruzzle_solver::pretty::{PrettyStyle, render_path, write_pretty};
+use ruzzle_solver::store::HashStore;
+
+fn board() -> Board {
+    let alphabet = Alphabet::english();
+    let lines = ["H U N T", "S R E A", "A B C D", "F G I J", "", "- - - -", "- 2 - -", "- - D -", "T - - 3"]
+        .iter().map(|line| line.to_string()).collect();
+    let (board, word_int_mults, letter_mults) = parse_board_and_mults(lines, &alphabet);
+    let store = Store::Hashed(HashStore::from_words(Default::default(), alphabet.bits_per_letter()));
+    Board::new(board, word_int_mults, letter_mults, Arc::new(Dictionary::new("test", alphabet, store)))
+}
+
+#[test]
+fn draws_numbered_path_and_multipliers() {
+    let path = vec_to_path(&[(1, 1), (0, 1), (0, 2)]);
+    assert_eq!(render_path(&board(), path, PrettyStyle::Plain), "\
++------+------+------+------+
+| H    | U  2 | N  3 | T    |
+|      |      |      |      |
++------+------+------+------+
+| S    | R  1 | E    | A    |
+|      |   DW |      |      |
++------+------+------+------+
+| A    | B    | C    | D    |
+|      |      |   DL |      |
++------+------+------+------+
+| F    | G    | I    | J    |
+|   TL |      |      |   TW |
++------+------+------+------+
+");
+}
+
+#[test]
+fn colours_path_and_writes_each_word() {
+    let board = board();
+    let path = vec_to_path(&[(1, 1), (0, 1), (0, 2)]);
+    let picture = render_path(&board, path, PrettyStyle::Colour);
+    assert!(picture.contains("\x1b[1;32m R  1 \x1b[0m"));
+    assert!(picture.contains("\x1b[1;33m U  2 \x1b[0m"));
+    assert!(picture.contains("\x1b[2m H    \x1b[0m"));
+    assert!(picture.contains("\x1b[35m   DW \x1b[0m"));
+
+    let mut out = Vec::new();
+    write_pretty(&mut out, &board, &[("RUN".to_string(), 8, path)], PrettyStyle::Plain).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.starts_with("RUN (8 points)\n+------+"));
+    assert_eq!(PrettyStyle::from_name("color"), Some(PrettyStyle::Colour));
+    assert_eq!(PrettyStyle::from_name("bold"), None);
+}
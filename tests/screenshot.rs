@@ -0,0 +1,73 @@
+use ruzzle_solver::parse_board_and_mults;
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::screenshot::{Palette, Region, Templates, read_board_image, read_png};
+
+const REGION: Region = Region { x: 20, y: 200, width: 360, height: 360 };
+
+fn read_fixture(name: &str, region: &Region) -> Result<Vec<String>, String> {
+    let image = read_png(&format!("tests/fixtures/{}", name)).unwrap();
+    read_board_image(&image, region, &Palette::default(), &Templates::bundled())
+}
+
+#[test]
+fn reads_letters_and_multipliers() {
+    let lines = read_fixture("board.png", &REGION).unwrap();
+    assert_eq!(lines, vec![
+        "H H S A", "U I R N", "N T A E", "E R I M",
+        "",
+        "2 - - -", "3 - T -", "- - 2 D", "- T - -",
+    ]);
+}
+
+#[test]
+fn recognises_the_rest_of_the_alphabet() {
+    let lines = read_fixture("alphabet.png", &REGION).unwrap();
+    assert_eq!(lines, vec![
+        "B C D F", "G J K L", "O P Q V", "W X Y Z",
+        "",
+        "D - T -", "- 2 - 3", "- - - -", "T - D 2",
+    ]);
+}
+
+#[test]
+fn recognises_accented_letters_of_the_built_in_alphabets() {
+    let lines = read_fixture("accents.png", &REGION).unwrap();
+    assert_eq!(lines, vec![
+        "Ä Ö Ü Å", "Ñ A O U", "N S T E", "R L I M",
+        "",
+        "- - - -", "- D - -", "- - 3 -", "T - - -",
+    ]);
+
+    // Templates for an alphabet only recognise its letters.
+    let image = read_png("tests/fixtures/accents.png").unwrap();
+    let german = Alphabet::german();
+    let lines = read_board_image(&image, &REGION, &Palette::default(), &Templates::for_alphabet(&german).unwrap());
+    assert!(lines.unwrap()[..4].iter().flat_map(|line| line.split(' ')).all(|letter| german.encode(letter).is_some()));
+    let letters = Alphabet::new("runic", &[('A', 1), ('ᚠ', 2)]).unwrap();
+    assert!(Templates::for_alphabet(&letters).is_err());
+}
+
+#[test]
+fn output_is_accepted_by_the_board_parser() {
+    let lines = read_fixture("board.png", &REGION).unwrap();
+    let (board, word_mults, letter_mults) = parse_board_and_mults(lines, &Alphabet::english());
+    assert_eq!(board[..4], [8, 8, 19, 1]);
+    assert_eq!(word_mults[..4], [2, 1, 1, 1]);
+    assert_eq!(letter_mults[4..8], [1, 1, 3, 1]);
+}
+
+#[test]
+fn region_outside_image_is_an_error() {
+    let region = Region { x: 200, ..REGION };
+    assert!(read_fixture("board.png", &region).is_err());
+}
+
+#[test]
+fn region_without_tiles_is_an_error() {
+    let region = Region { y: 0, height: 160, ..REGION };
+    assert!(read_fixture("board.png", &region).is_err());
+    let region = Region { x: 10, y: 10, width: 3, height: 3 };
+    assert!(read_fixture("board.png", &region).is_err());
+    let region = Region { x: u32::MAX, width: 2, ..REGION };
+    assert!(read_fixture("board.png", &region).is_err());
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::plan::TimeModel;
+use ruzzle_solver::random::{MultiplierCounts, Rng, random_board};
+use ruzzle_solver::simulate::{Bot, RoundResult, Simulation, simulate};
+
+const WORDS: [&str; 8] = ["AN", "AT", "TEN", "NET", "RATE", "TEAR", "STONE", "NOTES"];
+
+#[test]
+fn draws_the_same_board_from_the_same_seed() {
+    let alphabet = Alphabet::english();
+    let counts = MultiplierCounts { double_letter: 3, triple_letter: 2, double_word: 1, triple_word: 1 };
+    let (letters, word_mults, letter_mults) = random_board(&alphabet, &counts, &mut Rng::new(7));
+
+    assert_eq!(random_board(&alphabet, &counts, &mut Rng::new(7)), (letters, word_mults, letter_mults));
+    assert_ne!(random_board(&alphabet, &counts, &mut Rng::new(8)).0, letters);
+    assert!(letters.iter().all(|&code| (1..=alphabet.len() as u8).contains(&code)));
+    assert_eq!(word_mults.iter().filter(|&&mult| mult == 2).count(), 1);
+    assert_eq!(word_mults.iter().filter(|&&mult| mult == 3).count(), 1);
+    assert_eq!(letter_mults.iter().filter(|&&mult| mult == 2).count(), 3);
+    assert_eq!(letter_mults.iter().filter(|&&mult| mult == 3).count(), 2);
+    assert!((0..16).all(|tile| word_mults[tile] == 1 || letter_mults[tile] == 1));
+}
+
+#[test]
+fn bots_play_the_words_they_know_within_the_time() {
+    let word_info: Vec<(String, u16, u64)> = vec![
+        ("STONE".to_string(), 10, 0b10000_10001_10010_10011_10111),
+        ("TEN".to_string(), 3, 0b10000_10001_10010),
+        ("AN".to_string(), 2, 0b10000_10001),
+    ];
+    let model = TimeModel::default();
+
+    let expert = Bot::new("expert", 1., 1., 1.);
+    let words: Vec<String> = expert.play(&word_info, &model, 60., &mut Rng::new(1)).into_iter().map(|(word, _)| word).collect();
+    assert_eq!(words, ["STONE", "TEN", "AN"]);
+    assert!(expert.play(&word_info, &model, 0.5, &mut Rng::new(1)).is_empty());
+
+    let hasty = Bot::new("hasty", 1., 2., -1.);
+    assert_eq!(hasty.play(&word_info, &model, 0.5, &mut Rng::new(1)), [("AN".to_string(), 2)]);
+    assert!(Bot::new("blank", 0., 1., 0.).play(&word_info, &model, 60., &mut Rng::new(1)).is_empty());
+}
+
+#[test]
+fn parses_bots() {
+    assert_eq!("expert:0.9:1.5:0.5".parse(), Ok(Bot::new("expert", 0.9, 1.5, 0.5)));
+    assert!("expert:0.9:1.5".parse::<Bot>().is_err());
+    assert!("expert:1.5:1:0".parse::<Bot>().is_err());
+}
+
+#[test]
+fn simulations_repeat_from_the_same_seed() {
+    let dictionary = common::dictionary(&WORDS);
+    let bots = [Bot::new("slow", 0.5, 0.5, 0.), Bot::new("fast", 1., 2., 0.5)];
+    let simulation = simulate(&dictionary, &bots, 10, 42, 120., &TimeModel::default());
+
+    assert_eq!(simulation.rounds.len(), 10);
+    assert_eq!(simulate(&dictionary, &bots, 10, 42, 120., &TimeModel::default()), simulation);
+    assert!((simulation.win_rates().iter().sum::<f32>() - 1.).abs() < 1e-4);
+    assert!(simulation.rounds.iter().all(|round| round.scores[1] >= round.scores[0]));
+}
+
+#[test]
+fn shares_tied_rounds() {
+    let simulation = Simulation {
+        bots: vec!["a".to_string(), "b".to_string()],
+        rounds: vec![
+            RoundResult { board: "A".repeat(16), scores: vec![10, 5] },
+            RoundResult { board: "B".repeat(16), scores: vec![7, 7] },
+        ],
+    };
+    assert_eq!(simulation.win_rates(), [0.75, 0.25]);
+    assert_eq!(simulation.average_scores(), [8.5, 6.]);
+}
@@ -0,0 +1,301 @@
+mod common;
+
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+use proptest::prelude::*;
+use ruzzle_solver::{Board, BOARD_SIZE, parse_to_str, path_to_vec, vec_to_path};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::Dictionary;
+use ruzzle_solver::random::{MultiplierCounts, Rng, random_board};
+use ruzzle_solver::strategy::{STRATEGY_NAMES, strategy_by_name};
+
+const WORDS: [&str; 40] = [
+    "AE", "AN", "AR", "AT", "EN", "ER", "ES", "IN", "IT", "NA", "NE", "RE", "TA", "TE", "TI", "ATE", "EAR", "EAT",
+    "ERA", "ERN", "NET", "RAN", "RAT", "SEA", "SET", "TEA", "TEN", "TIN", "EARN", "NEAR", "RATE", "REST", "SENT",
+    "STAR", "TEAR", "TERN", "STARE", "STERN", "TREAT", "INERT",
+];
+
+fn is_adjacent((x1, y1): (u8, u8), (x2, y2): (u8, u8)) -> bool {
+    (x1, y1) != (x2, y2) && x1.abs_diff(x2) <= 1 && y1.abs_diff(y2) <= 1
+}
+
+/// Returns the cells next to each cell, diagonals included, indexed by tile.
+fn neighbours() -> Vec<Vec<(u8, u8)>> {
+    let cells: Vec<(u8, u8)> = (0..BOARD_SIZE as u8).flat_map(|x| (0..BOARD_SIZE as u8).map(move |y| (x, y))).collect();
+    cells.iter().map(|&from| cells.iter().copied().filter(|&to| is_adjacent(from, to)).collect()).collect()
+}
+
+/// Words of 9 to 12 letters, and some of their prefixes, for boards they are written into.
+const LONG_WORDS: [&str; 12] = [
+    "ENTERTAIN", "ENTERTAINER", "ENTERTAINERS", "INTERSTATE", "RESTATE", "RESTATEMENT", "RESTATEMENTS",
+    "STATIONERS", "STRENGTHEN", "STRENGTHENS", "TRANSIENT", "TRANSIENTS",
+];
+
+/// A word found by the reference solver, with its path and score.
+type Found = BTreeSet<(String, Vec<(u8, u8)>, u16)>;
+
+/// The words the reference solver looks for, the length of the longest of them, and, if paths are
+/// pruned, every prefix of them.
+struct Reference {
+    words: HashSet<String>,
+    max_len: usize,
+    prefixes: Option<HashSet<String>>,
+}
+
+impl Reference {
+    /// Tries every path, with no pruning other than stopping at the length of the longest word.
+    fn exhaustive<'a>(words: impl IntoIterator<Item = &'a str>) -> Reference {
+        let words: HashSet<String> = words.into_iter().map(str::to_string).collect();
+        let max_len = words.iter().map(String::len).max().unwrap_or(0);
+        Reference { words, max_len, prefixes: None }
+    }
+
+    /// Stops extending a path once its letters start no word.
+    fn pruned<'a>(words: impl IntoIterator<Item = &'a str>) -> Reference {
+        let words: HashSet<String> = words.into_iter().map(str::to_string).collect();
+        let max_len = words.iter().map(String::len).max().unwrap_or(0);
+        let prefixes = words.iter().flat_map(|word| (1..word.len()).map(move |len| word[..len].to_string())).collect();
+        Reference { words, max_len, prefixes: Some(prefixes) }
+    }
+
+    /// Finds every word in board, scoring each from the letter values of its alphabet and the
+    /// multipliers of the cells it crosses.
+    fn solve(&self, board: &Board) -> Found {
+        let graph = neighbours();
+        let mut found = BTreeSet::new();
+        for x in 0..BOARD_SIZE as u8 {
+            for y in 0..BOARD_SIZE as u8 {
+                let mut word = board.alphabet().symbol(board.board[x as usize * BOARD_SIZE + y as usize]).to_string();
+                self.walk(board, &graph, &mut word, &mut vec![(x, y)], &mut found);
+            }
+        }
+        found
+    }
+
+    /// Extends word and path to every cell next to the last cell of path, recording each word.
+    fn walk(&self, board: &Board, graph: &[Vec<(u8, u8)>], word: &mut String, path: &mut Vec<(u8, u8)>,
+            found: &mut Found) {
+        let tile = |(x, y): (u8, u8)| x as usize * BOARD_SIZE + y as usize;
+        if path.len() >= 2 && self.words.contains(word.as_str()) {
+            let points: u64 = path.iter()
+                .map(|&cell| board.alphabet().value(board.board[tile(cell)]) as u64 * board.letter_mults[tile(cell)] as u64)
+                .sum();
+            let mult: u64 = path.iter().map(|&cell| board.word_int_mults[tile(cell)] as u64).product();
+            let bonus = 5 * path.len().saturating_sub(4) as u64;
+            found.insert((word.clone(), path.clone(), (points * mult + bonus).min(u16::MAX as u64) as u16));
+        }
+        if path.len() >= self.max_len
+            || self.prefixes.as_ref().is_some_and(|prefixes| !prefixes.contains(word.as_str())) {
+            return;
+        }
+        for &cell in &graph[tile(*path.last().unwrap())] {
+            if !path.contains(&cell) {
+                word.push(board.alphabet().symbol(board.board[tile(cell)]));
+                path.push(cell);
+                self.walk(board, graph, word, path, found);
+                path.pop();
+                word.pop();
+            }
+        }
+    }
+}
+
+fn solved(dictionary: &Arc<Dictionary>, seed: u64) -> Board {
+    let (letters, word_int_mults, letter_mults) =
+        random_board(&dictionary.alphabet, &MultiplierCounts::default(), &mut Rng::new(seed));
+    let mut board = Board::new(letters, word_int_mults, letter_mults, Arc::clone(dictionary));
+    board.solve();
+    board
+}
+
+/// A random board with word written along a random path through it.
+fn planted(dictionary: &Arc<Dictionary>, word: &str, seed: u64) -> Board {
+    let mut rng = Rng::new(seed);
+    let (mut letters, word_int_mults, letter_mults) =
+        random_board(&dictionary.alphabet, &MultiplierCounts::default(), &mut rng);
+    let path = loop {
+        let steps: Vec<usize> = (1..word.len()).map(|_| rng.below(8)).collect();
+        let path = walk(rng.below(BOARD_SIZE * BOARD_SIZE) as u8, &steps);
+        if path.len() == word.len() {
+            break path;
+        }
+    };
+    for (&(x, y), letter) in path.iter().zip(word.chars()) {
+        letters[x as usize * BOARD_SIZE + y as usize] = dictionary.alphabet.code(letter).unwrap();
+    }
+    let mut board = Board::new(letters, word_int_mults, letter_mults, Arc::clone(dictionary));
+    board.solve();
+    board
+}
+
+/// Checks that board found what the reference solver finds, and returns what it found.
+fn assert_matches_reference(board: &Board, reference: &Reference, seed: u64) -> Found {
+    let words: Found = board.word_info.iter()
+        .map(|(word, score, path)| (word.clone(), path_to_vec(*path), *score))
+        .collect();
+    assert_eq!(words.len(), board.word_info.len(), "board {} has a repeated path", seed);
+    assert_eq!(words, reference.solve(board), "board {} differs", seed);
+    words
+}
+
+#[test]
+fn matches_a_reference_solver_on_random_boards() {
+    let dictionary = common::dictionary(&WORDS);
+    let reference = Reference::exhaustive(WORDS);
+    let mut found = 0;
+    for seed in 0..2000 {
+        found += assert_matches_reference(&solved(&dictionary, seed), &reference, seed).len();
+    }
+    assert!(found > 10000, "only {} words were found", found);
+}
+
+#[test]
+fn matches_a_reference_solver_on_boards_with_long_words() {
+    let words: Vec<&str> = WORDS.iter().chain(&LONG_WORDS).copied().collect();
+    let dictionary = common::dictionary(&words);
+    let reference = Reference::pruned(words.iter().copied());
+    let mut longest = 0;
+    for seed in 0..1000 {
+        let board = planted(&dictionary, LONG_WORDS[seed as usize % LONG_WORDS.len()], seed);
+        let found = assert_matches_reference(&board, &reference, seed);
+        longest += found.iter().filter(|(word, _, _)| word.len() == 12).count();
+    }
+    assert!(longest >= 100, "only {} words of 12 letters were found", longest);
+}
+
+#[test]
+fn matches_a_reference_solver_with_the_shipped_word_list() {
+    let text = std::fs::read_to_string("data/TWL06/TWL06Trimmed.txt").unwrap();
+    let words: Vec<&str> = text.lines().map(str::trim).filter(|word| word.len() <= 12).collect();
+    let long: Vec<&str> = words.iter().copied().filter(|word| word.len() >= 9).collect();
+    let dictionary = common::shipped_dictionary();
+    let reference = Reference::pruned(words.iter().copied());
+    let mut found = 0;
+    let mut longest = 0;
+    for seed in 0..100 {
+        let board = if seed % 2 == 0 {
+            solved(&dictionary, seed)
+        } else {
+            planted(&dictionary, long[Rng::new(seed).below(long.len())], seed)
+        };
+        let words = assert_matches_reference(&board, &reference, seed);
+        found += words.len();
+        longest += words.iter().filter(|(word, _, _)| word.len() >= 9).count();
+    }
+    assert!(found > 10000, "only {} words were found", found);
+    assert!(longest >= 50, "only {} words of 9 letters or more were found", longest);
+}
+
+/// The words of the longest length in LONGEST_BOARD, and some of their prefixes.
+const LONGEST_WORDS: [&str; 4] = ["SNAKINGWORDS", "SNAKINGWORD", "SNAKING", "SNAK"];
+
+/// SNAKINGWORDS winds along the first three rows, with a double word on its last letter.
+const LONGEST_BOARD: [&str; 2] = ["SNAK WGNI ORDS FFFF", "---- ---- ---2 ----"];
+
+/// Returns the words found in board, best first and then alphabetically.
+fn sorted(mut words: Vec<(String, u16, u64)>) -> Vec<(String, u16, u64)> {
+    words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.cmp(b)));
+    words
+}
+
+#[test]
+fn scores_words_of_the_longest_length() {
+    let mut board = common::board(&common::dictionary(&LONGEST_WORDS), &LONGEST_BOARD);
+    board.solve();
+
+    let found: Vec<(String, u16)> = sorted(board.word_info).into_iter().map(|(word, score, _)| (word, score)).collect();
+    // S N A K I N G W O R D S scores 1+1+1+5+1+1+3+4+1+1+2+1 = 22 points, doubled by its last
+    // letter, and 5 for each letter past the fourth.
+    assert_eq!(found[..2], [("SNAKINGWORDS".to_string(), 2 * 22 + 40), ("SNAKINGWORD".to_string(), 21 + 35)]);
+}
+
+#[test]
+fn every_search_agrees_on_words_of_the_longest_length() {
+    let dictionary = common::dictionary(&LONGEST_WORDS);
+    let mut board = common::board(&dictionary, &LONGEST_BOARD);
+    board.solve();
+    let expected = sorted(board.word_info.clone());
+    assert!(expected.iter().any(|(word, _, _)| word.len() == 12));
+
+    for name in STRATEGY_NAMES {
+        strategy_by_name(name, &dictionary).unwrap().solve(&mut board);
+        assert_eq!(sorted(board.word_info.clone()), expected, "{} differs", name);
+    }
+    let mut streamed = Vec::new();
+    board.solve_streaming(None, None, &mut streamed);
+    assert_eq!(sorted(streamed), expected, "the streaming search differs");
+    board.solve_min_score(0);
+    assert_eq!(sorted(board.word_info.clone()), expected, "the minimum score search differs");
+    board.solve_top_k(1);
+    assert_eq!(board.word_info, expected[..1], "the top-k search differs");
+}
+
+#[test]
+fn word_multipliers_do_not_overflow() {
+    // Every path of AAAAAA crosses six triple words, for 6 * 3^6 points and 10 for its length.
+    let dictionary = common::dictionary(&["AAAAAA"]);
+    let mut board = common::board(&dictionary, &["AAAA AAAA AAAA AAAA", "3333 3333 3333 3333"]);
+    let expected = 6 * 729 + 10;
+
+    for name in STRATEGY_NAMES {
+        strategy_by_name(name, &dictionary).unwrap().solve(&mut board);
+        assert!(!board.word_info.is_empty(), "{} found nothing", name);
+        assert!(board.word_info.iter().all(|(_, score, _)| *score == expected), "{} overflowed", name);
+    }
+    let mut streamed = Vec::new();
+    board.solve_streaming(None, None, &mut streamed);
+    assert_eq!(streamed.len(), board.word_info.len());
+    assert!(streamed.iter().all(|(_, score, _)| *score == expected));
+    board.solve_min_score(expected);
+    assert_eq!(board.word_info.len(), streamed.len());
+    board.solve_top_k(1);
+    assert_eq!(board.word_info[0].1, expected);
+}
+
+/// A path of up to 12 distinct adjacent cells, walked from start by picking one of the unvisited
+/// neighbours at each step.
+fn walk(start: u8, steps: &[usize]) -> Vec<(u8, u8)> {
+    let mut path = vec![(start / 4, start % 4)];
+    for &step in steps {
+        let last = *path.last().unwrap();
+        let next: Vec<(u8, u8)> = neighbours()[last.0 as usize * BOARD_SIZE + last.1 as usize].iter()
+            .copied()
+            .filter(|cell| !path.contains(cell))
+            .collect();
+        if next.is_empty() {
+            break;
+        }
+        path.push(next[step % next.len()]);
+    }
+    path
+}
+
+proptest! {
+    #[test]
+    fn packed_words_decode_to_the_same_letters(word in "[A-Z]{1,12}") {
+        let encoded = Alphabet::english().encode(&word).unwrap();
+        prop_assert_eq!(parse_to_str(encoded), word.clone());
+        prop_assert_eq!(Alphabet::english().decode(encoded), word);
+    }
+
+    #[test]
+    fn packed_paths_decode_to_the_same_cells(start in 0u8..16, steps in prop::collection::vec(0usize..8, 0..12)) {
+        let path = walk(start, &steps);
+        prop_assert_eq!(path_to_vec(vec_to_path(&path)), path);
+    }
+
+    #[test]
+    fn found_paths_spell_their_words_through_distinct_adjacent_cells(seed in any::<u64>()) {
+        let board = solved(&common::dictionary(&WORDS), seed);
+        for (word, _, path) in &board.word_info {
+            let cells = path_to_vec(*path);
+            prop_assert!(cells.windows(2).all(|pair| is_adjacent(pair[0], pair[1])), "{} is not adjacent", word);
+            let distinct: HashSet<&(u8, u8)> = cells.iter().collect();
+            prop_assert_eq!(distinct.len(), cells.len(), "{} reuses a cell", word);
+            let spelled: String = cells.iter()
+                .map(|&(x, y)| board.alphabet().symbol(board.board[x as usize * BOARD_SIZE + y as usize]))
+                .collect();
+            prop_assert_eq!(&spelled, word);
+        }
+    }
+}
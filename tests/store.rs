@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use ruzzle_solver::{Board, parse_board_and_mults};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::{Dictionary, Store};
+use ruzzle_solver::store::{HashStore, Trie, WordStore};
+
+const WORDS: [&str; 6] = ["HUNT", "HUNTER", "HUNTERS", "TREE", "RUN", "HUT"];
+
+fn board_lines() -> Vec<String> {
+    ["H U N T", "S R E A", "A B C D", "F G I J", "", "- - - -", "- 2 - -", "- - D -", "T - - -"]
+        .iter().map(|line| line.to_string()).collect()
+}
+
+fn packed_words(alphabet: &Alphabet) -> Vec<u64> {
+    WORDS.iter().map(|word| alphabet.encode(word).unwrap()).collect()
+}
+
+fn solve(store: Store) -> Vec<(String, u16, u64)> {
+    let alphabet = Alphabet::english();
+    let (board, word_int_mults, letter_mults) = parse_board_and_mults(board_lines(), &alphabet);
+    let dictionary = Arc::new(Dictionary::new("test", alphabet, store));
+    let mut board = Board::new(board, word_int_mults, letter_mults, dictionary);
+    board.solve();
+    board.sort_entries();
+    board.word_info.sort();
+    board.word_info
+}
+
+/// A store which any team could drop in: an ordered set, walked by the packed word so far.
+struct BTreeStore {
+    words: BTreeSet<u64>,
+}
+
+impl WordStore for BTreeStore {
+    type State = u64;
+
+    fn bits_per_letter(&self) -> u32 {
+        5
+    }
+
+    fn start(&self) -> u64 {
+        0
+    }
+
+    fn step(&self, word: u64, code: u8) -> Option<u64> {
+        let next = (word << 5) | code as u64;
+        self.words.iter().any(|word| (0..12).any(|shift| word >> (5 * shift) == next)).then_some(next)
+    }
+
+    fn ends_word(&self, word: u64) -> bool {
+        self.words.contains(&word)
+    }
+}
+
+#[test]
+fn trie_walks_words_and_prefixes() {
+    let alphabet = Alphabet::english();
+    let trie = Trie::from_words(packed_words(&alphabet), 5);
+    let encode = |word: &str| alphabet.encode(word).unwrap();
+
+    assert!(trie.is_word(encode("HUNTER")));
+    assert!(!trie.is_word(encode("HUNTE")));
+    assert!(trie.is_prefix(encode("HUNTE")));
+    assert!(!trie.is_prefix(encode("HUNTS")));
+
+    let hun = trie.walk(encode("HUN")).unwrap();
+    let hunt = trie.step(hun, alphabet.code('T').unwrap()).unwrap();
+    assert!(trie.ends_word(hunt));
+    assert!(trie.step(hunt, alphabet.code('Z').unwrap()).is_none());
+
+    let mut words = trie.words();
+    words.sort_unstable();
+    let mut expected = packed_words(&alphabet);
+    expected.sort_unstable();
+    assert_eq!(words, expected);
+}
+
+#[test]
+fn stores_find_the_same_words() {
+    let alphabet = Alphabet::english();
+    let words = packed_words(&alphabet).into_iter().collect();
+    let hashed = solve(Store::Hashed(HashStore::from_words(words, 5)));
+    let trie = solve(Store::Trie(Trie::from_words(packed_words(&alphabet), 5)));
+
+    let found: Vec<&str> = hashed.iter().map(|(word, _, _)| word.as_str()).collect();
+    assert!(found.contains(&"HUNTERS"));
+    assert!(found.contains(&"RUN"));
+    assert_eq!(hashed, trie);
+}
+
+#[test]
+fn boards_can_be_solved_with_other_stores() {
+    let alphabet = Alphabet::english();
+    let words = packed_words(&alphabet).into_iter().collect();
+    let mut expected = solve(Store::Hashed(HashStore::from_words(words, 5)));
+
+    let (board, word_int_mults, letter_mults) = parse_board_and_mults(board_lines(), &alphabet);
+    let dictionary = Arc::new(Dictionary::new("empty", alphabet.clone(), Store::Trie(Trie::from_words([], 5))));
+    let mut board = Board::new(board, word_int_mults, letter_mults, dictionary);
+    board.solve_with(&BTreeStore { words: packed_words(&alphabet).into_iter().collect() });
+    board.word_info.sort();
+    expected.sort();
+
+    assert_eq!(board.word_info, expected);
+}
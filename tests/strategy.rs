@@ -0,0 +1,81 @@
+mod common;
+
+use std::sync::Arc;
+use ruzzle_solver::Board;
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::{Dictionary, Store};
+use ruzzle_solver::random::{MultiplierCounts, Rng, random_board};
+use ruzzle_solver::store::Trie;
+use ruzzle_solver::strategy::{STRATEGY_NAMES, SearchStrategy, strategy_by_name};
+
+// RATTERSNATEN is made up, and winds through the first three rows of the fixed board, so that the
+// strategies are compared on a word of the longest length.
+const WORDS: [&str; 25] = ["AE", "AN", "AT", "EAT", "ERA", "NET", "RAN", "RAT", "SEA", "SET", "TEA", "TEN", "EARN",
+                           "NEAR", "RATE", "REST", "SENT", "STAR", "TEAR", "TERN", "STARE", "STERN", "TREAT", "RATTERS",
+                           "RATTERSNATEN"];
+
+/// The dictionary of WORDS, held in a trie or, as in the other tests, a hash set.
+fn dictionary(trie: bool) -> Arc<Dictionary> {
+    if !trie {
+        return common::dictionary(&WORDS);
+    }
+    let alphabet = Alphabet::english();
+    let words: Vec<u64> = WORDS.iter().map(|word| alphabet.encode(word).unwrap()).collect();
+    let store = Store::Trie(Trie::from_words(words, alphabet.bits_per_letter()));
+    Arc::new(Dictionary::new("test", alphabet, store))
+}
+
+/// Solves board with strategy, returning the words found in a fixed order.
+fn solve(strategy: &dyn SearchStrategy, board: &mut Board) -> Vec<(String, u16, u64)> {
+    strategy.solve(board);
+    let mut words = board.word_info.clone();
+    words.sort();
+    words
+}
+
+fn strategies(dictionary: &Dictionary) -> Vec<Box<dyn SearchStrategy>> {
+    STRATEGY_NAMES.iter().map(|name| strategy_by_name(name, dictionary).unwrap()).collect()
+}
+
+#[test]
+fn finds_the_same_words_on_a_fixed_board() {
+    let dictionary = dictionary(false);
+    let mut board = common::board(&dictionary, &["RATT NSRE ATEN STAR", "---- -2-- --D- T--3"]);
+
+    let strategies = strategies(&dictionary);
+    let expected = solve(strategies[0].as_ref(), &mut board);
+    assert!(expected.iter().any(|(word, _, _)| word == "RATTERS"));
+    assert!(expected.iter().any(|(word, _, _)| word == "RATTERSNATEN"));
+    for strategy in &strategies[1..] {
+        assert_eq!(solve(strategy.as_ref(), &mut board), expected, "{} differs from stack", strategy.name());
+    }
+}
+
+#[test]
+fn finds_the_same_words_on_random_boards() {
+    let mut found = 0;
+    for trie in [false, true] {
+        let dictionary = dictionary(trie);
+        let strategies = strategies(&dictionary);
+        for seed in 0..200 {
+            let (letters, word_int_mults, letter_mults) =
+                random_board(&dictionary.alphabet, &MultiplierCounts::default(), &mut Rng::new(seed));
+            let mut board = Board::new(letters, word_int_mults, letter_mults, Arc::clone(&dictionary));
+            let expected = solve(strategies[0].as_ref(), &mut board);
+            found += expected.len();
+            for strategy in &strategies[1..] {
+                assert_eq!(solve(strategy.as_ref(), &mut board), expected, "{} differs on board {}", strategy.name(), seed);
+            }
+        }
+    }
+    assert!(found > 1000, "only {} words were found", found);
+}
+
+#[test]
+fn picks_strategies_by_name() {
+    let dictionary = dictionary(false);
+    for name in STRATEGY_NAMES {
+        assert_eq!(strategy_by_name(name, &dictionary).unwrap().name(), name);
+    }
+    assert!(strategy_by_name("quantum", &dictionary).is_none());
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+use ruzzle_solver::stream::SolveStatus;
+
+fn sorted(mut words: Vec<(String, u16, u64)>) -> Vec<(String, u16, u64)> {
+    words.sort();
+    words
+}
+
+#[test]
+fn a_sender_receives_the_same_words_as_solve() {
+    let mut board = common::dense_board();
+    let (sender, receiver) = mpsc::channel();
+    let status = board.solve_streaming(None, None, &mut sender.clone());
+    drop(sender);
+    assert_eq!(status, SolveStatus::Complete);
+    assert!(status.is_complete());
+
+    board.solve();
+    assert!(board.word_info.len() > 500, "only {} words were found", board.word_info.len());
+    assert_eq!(sorted(receiver.into_iter().collect()), sorted(board.word_info));
+}
+
+#[test]
+fn a_set_cancel_flag_stops_the_search_before_any_word() {
+    let board = common::dense_board();
+    let cancel = AtomicBool::new(true);
+    let mut words = Vec::new();
+    assert_eq!(board.solve_streaming(None, Some(&cancel), &mut words), SolveStatus::Cancelled);
+    assert!(words.is_empty());
+}
+
+#[test]
+fn cancelling_during_the_search_stops_it_early() {
+    let board = common::dense_board();
+    let cancel = AtomicBool::new(false);
+    let mut count = 0;
+    let status = board.solve_streaming(None, Some(&cancel), &mut |_: String, _: u16, _: u64| {
+        count += 1;
+        cancel.store(true, Ordering::Relaxed);
+    });
+    assert_eq!(status, SolveStatus::Cancelled);
+    assert!(!status.is_complete());
+
+    let mut all = Vec::new();
+    board.solve_streaming(None, None, &mut all);
+    assert!(count >= 1 && count < all.len(), "{} of {} words were found", count, all.len());
+}
+
+#[test]
+fn a_zero_time_budget_times_out() {
+    let board = common::dense_board();
+    let mut words = Vec::new();
+    assert_eq!(board.solve_streaming(Some(Duration::ZERO), None, &mut words), SolveStatus::TimedOut);
+    assert!(words.is_empty());
+
+    let cancel = AtomicBool::new(false);
+    assert_eq!(board.solve_streaming(Some(Duration::from_secs(600)), Some(&cancel), &mut words), SolveStatus::Complete);
+    assert!(!words.is_empty());
+}
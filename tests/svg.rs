@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use ruzzle_solver::{Board, parse_board_and_mults, vec_to_path};
+use ruzzle_solver::alphabet::Alphabet;
+use ruzzle_solver::dictionary::{Dictionary, Store};
+use ruzzle_solver::store::HashStore;
+use ruzzle_solver::svg::render_svg;
+
+fn board() -> Board {
+    let alphabet = Alphabet::english();
+    let lines = ["H U N T", "S R E A", "A B C D", "F G I J", "", "- - - -", "- 2 - -", "- - D -", "T - - 3"]
+        .iter().map(|line| line.to_string()).collect();
+    let (board, word_int_mults, letter_mults) = parse_board_and_mults(lines, &alphabet);
+    let store = Store::Hashed(HashStore::from_words(Default::default(), alphabet.bits_per_letter()));
+    Board::new(board, word_int_mults, letter_mults, Arc::new(Dictionary::new("test", alphabet, store)))
+}
+
+fn run() -> (String, u16, u64) {
+    ("RUN".to_string(), 8, vec_to_path(&[(1, 1), (0, 1), (0, 2)]))
+}
+
+#[test]
+fn draws_tiles_badges_and_swipe() {
+    let svg = render_svg(&board(), &[run()]);
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains(r#"width="384" height="424""#));
+    assert!(svg.contains(">RUN (8 points)</text>"));
+    assert_eq!(svg.matches(r#"rx="10""#).count(), 16);
+    for badge in [">DW<", ">DL<", ">TL<", ">TW<"] {
+        assert_eq!(svg.matches(badge).count(), 1, "{}", badge);
+    }
+    // The swipe runs through the centres of R, U and N.
+    assert!(svg.contains(r#"<polyline points="148,188 148,100 236,100""#));
+    assert!(svg.contains(r#"<circle cx="148" cy="188" r="30""#));
+}
+
+#[test]
+fn lays_words_out_in_rows_of_three() {
+    let svg = render_svg(&board(), &vec![run(); 4]);
+    assert!(svg.contains(r#"width="1152" height="848""#));
+    assert_eq!(svg.matches("<polyline").count(), 4);
+    assert_eq!(svg.matches(r#"rx="10""#).count(), 64);
+}
+
+#[test]
+fn escapes_letters_and_words_with_a_meaning_in_xml() {
+    let alphabet = Alphabet::new("symbols", &[('A', 1), ('&', 2), ('<', 3), ('>', 4), ('"', 5)]).unwrap();
+    let mut letters = [1; 16];
+    letters[..5].copy_from_slice(&[2, 3, 4, 5, 1]);
+    let store = Store::Hashed(HashStore::from_words(Default::default(), alphabet.bits_per_letter()));
+    let board = Board::new(letters, [1; 16], [1; 16], Arc::new(Dictionary::new("symbols", alphabet, store)));
+
+    let word = ("&<>".to_string(), 9, vec_to_path(&[(0, 0), (0, 1), (0, 2)]));
+    let svg = render_svg(&board, &[word]);
+    assert!(svg.contains(">&amp;&lt;&gt; (9 points)</text>"));
+    for letter in [">&amp;<", ">&lt;<", ">&gt;<", ">&quot;<"] {
+        assert_eq!(svg.matches(letter).count(), 1, "{}", letter);
+    }
+    // Every & starts an entity, and the only < and > are those of the tags.
+    let text: String = svg.split('<').skip(1).map(|tag| tag.split_once('>').unwrap().1).collect();
+    assert!(!text.contains(['<', '>', '"']));
+    assert_eq!(svg.matches('&').count(), svg.matches("&amp;").count() + svg.matches("&lt;").count()
+        + svg.matches("&gt;").count() + svg.matches("&quot;").count());
+    #[cfg(feature = "png-export")]
+    assert!(ruzzle_solver::svg::svg_to_png(&svg).is_ok());
+}
+
+#[cfg(feature = "png-export")]
+#[test]
+fn rasterizes_to_png() {
+    let png = ruzzle_solver::svg::svg_to_png(&render_svg(&board(), &[run()])).unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use std::fs;
+use std::io::{self, BufReader, Cursor, Read};
+use std::thread;
+use std::time::{Duration, Instant};
+use ruzzle_solver::Board;
+use ruzzle_solver::trainer::{Guess, HistoryEntry, Round, TimedLines, append_history, play_round, read_history, write_progress};
+
+/// A double word on the L and a triple letter on the K. ZEBRA is a word, but not in the board,
+/// and INK is worth almost as much as INKS.
+const BOARD: [&str; 2] = ["PLAY ROSE INKS TUBE", "-2-- ---- --T- ----"];
+const WORDS: [&str; 7] = ["INK", "INKS", "PLAY", "PROSE", "ROSE", "TUBE", "ZEBRA"];
+
+fn solved_board() -> Board {
+    let mut board = common::board(&common::dictionary(&WORDS), &BOARD);
+    board.solve();
+    board
+}
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("ruzzle_solver_{}_{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn guesses_are_checked_and_reported() {
+    let board = solved_board();
+    let mut round = Round::new(&board);
+    assert_eq!(round.guess("play"), Guess::Scored(20));
+    assert_eq!(round.guess("PLAY "), Guess::Repeated);
+    assert_eq!(round.guess("zebra"), Guess::NotInBoard);
+    // PLAYS can be swiped, but is not in the dictionary.
+    assert_eq!(round.guess("plays"), Guess::NotAWord);
+    assert_eq!(round.guess("inks"), Guess::Scored(18));
+    assert_eq!(round.found(), [("PLAY".to_string(), 20), ("INKS".to_string(), 18)]);
+
+    let report = round.report(3);
+    assert_eq!((report.score, report.best_score), (38, 80));
+    assert_eq!((report.found_words, report.total_words), (2, 6));
+    assert_eq!(report.missed, [("INK".to_string(), 17), ("PROSE".to_string(), 13), ("TUBE".to_string(), 8)]);
+    assert!((report.percentage() - 47.5).abs() < 0.1);
+}
+
+#[test]
+fn words_typed_after_the_round_are_not_counted() {
+    let board = solved_board();
+    let mut out = Vec::new();
+    let report = play_round(&board, Duration::from_secs(600), &mut lines("play\n\nplay\nzzz\n"), &mut out).unwrap().report(10);
+    assert_eq!(report.score, 20);
+    assert_eq!(String::from_utf8(out).unwrap(),
+               "You have 600s. Type one word per line.\n+20\nYou already found that word.\nThat is not a word.\n");
+
+    let mut out = Vec::new();
+    let mut input = lines("play\n");
+    let report = play_round(&board, Duration::ZERO, &mut input, &mut out).unwrap().report(10);
+    assert_eq!(report.score, 0);
+    assert!(String::from_utf8(out).unwrap().ends_with("Time is up.\n"));
+    assert_eq!(input.next().unwrap().unwrap(), "play");
+}
+
+fn lines(input: &'static str) -> TimedLines {
+    TimedLines::new(Cursor::new(input))
+}
+
+/// Input which the player stops typing into for a while, before typing text.
+struct Pause(Duration, Cursor<&'static str>);
+
+impl Read for Pause {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        thread::sleep(std::mem::take(&mut self.0));
+        self.1.read(buf)
+    }
+}
+
+#[test]
+fn rounds_end_on_time_when_the_player_stops_typing() {
+    let board = solved_board();
+    let pause = Pause(Duration::from_secs(5), Cursor::new("inks\n"));
+    let mut input = TimedLines::new(BufReader::new(Cursor::new("ink\n").chain(pause)));
+    let start = Instant::now();
+    let mut out = Vec::new();
+    let round = play_round(&board, Duration::from_millis(200), &mut input, &mut out).unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(2), "the round took {:?}", start.elapsed());
+    assert_eq!(round.found(), [("INK".to_string(), 17)]);
+    assert!(String::from_utf8(out).unwrap().ends_with("+17\nTime is up.\n"));
+}
+
+#[test]
+fn lines_typed_after_the_round_are_left_for_the_next_read() {
+    let board = solved_board();
+    let pause = Pause(Duration::from_millis(500), Cursor::new("inks\nnext\n"));
+    let mut input = TimedLines::new(BufReader::new(Cursor::new("ink\n").chain(pause)));
+    let mut out = Vec::new();
+    let round = play_round(&board, Duration::from_millis(100), &mut input, &mut out).unwrap();
+    assert_eq!(round.found(), [("INK".to_string(), 17)]);
+
+    let rest: Vec<String> = input.map(Result::unwrap).collect();
+    assert_eq!(rest, ["inks", "next"]);
+}
+
+#[test]
+fn history_is_kept_between_rounds() {
+    let path = temp_path("history.tsv");
+    assert_eq!(read_history(&path), Ok(Vec::new()));
+
+    let board = solved_board();
+    let mut round = Round::new(&board);
+    round.guess("play");
+    let first = HistoryEntry::new(&board, &round.report(0));
+    round.guess("inks");
+    round.guess("tube");
+    let second = HistoryEntry::new(&board, &round.report(0));
+    append_history(&path, &first).unwrap();
+    append_history(&path, &second).unwrap();
+
+    let history = read_history(&path).unwrap();
+    assert_eq!(history, [first, second]);
+    assert_eq!(history[0].board, "PLAYROSEINKSTUBE");
+    assert_eq!(history[0].dictionary, "test");
+
+    let mut out = Vec::new();
+    write_progress(&history, 1, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(),
+               "In your last round you scored 57.5%, against 25.0% before that. Your best round scored 46 points.\n");
+
+    fs::write(&path, "12\ttest\n").unwrap();
+    assert!(read_history(&path).is_err());
+}